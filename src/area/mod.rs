@@ -1,8 +1,7 @@
-use crate::{chance::Weighting, AppState, Title};
+use crate::{chance::Weighting, loading::RonAssetPlugin, AppState, RunSet, Title};
 use bevy::prelude::*;
-use ron::de::from_reader;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, fs::File};
+use std::fmt::Display;
 
 pub struct AreaPlugin;
 impl Plugin for AreaPlugin {
@@ -11,10 +10,14 @@ impl Plugin for AreaPlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app.insert_resource(Areas::init())
+        app.add_plugins(RonAssetPlugin::<Areas>::new(&["areas.ron"]))
             .init_resource::<CurrentArea>()
             .init_resource::<CurrentAreaSet>()
             .add_event::<SetArea>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                set_starting_area.in_set(RunSet::Reset),
+            )
             .add_systems(Update, evr_set_area.run_if(in_state(AppState::Playing)));
     }
 }
@@ -25,26 +28,24 @@ impl CurrentArea {}
 
 #[derive(Resource, Default, Deref, DerefMut)]
 pub struct CurrentAreaSet(pub Vec<Area>);
-
-#[derive(Resource, Default, Serialize, Deserialize)]
-pub struct Areas(pub Vec<Area>);
-impl Areas {
-    fn init() -> Self {
-        let input_path = format!("{}/ron/areas.ron", env!("CARGO_MANIFEST_DIR"));
-        let f = File::open(input_path.clone()).expect("Failed opening file");
-        let areas: Areas = match from_reader(f) {
-            Ok(x) => {
-                info!("[INITIALIZED] Areas: {}", x);
-                x
-            }
-            Err(e) => {
-                eprintln!("[ERROR] Could not deserialize {}. \n{}", input_path, e);
-                Areas::default()
-            }
-        };
-        areas
+impl CurrentAreaSet {
+    // COLLECTS EVERY AREA OF THE GIVEN KIND AND RETURNS THE FIRST
+    // LEAVES THE SET UNTOUCHED WHEN THERE ARE NONE
+    pub fn set(&mut self, kind: AreaKind, areas: &Areas) -> Option<Area> {
+        let set: Vec<Area> = areas
+            .0
+            .iter()
+            .filter(|area| area.kind == kind)
+            .cloned()
+            .collect();
+        let first = set.first()?.clone();
+        self.0 = set;
+        Some(first)
     }
 }
+
+#[derive(Asset, TypePath, Resource, Default, Serialize, Deserialize)]
+pub struct Areas(pub Vec<Area>);
 impl Display for Areas {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut string: String = String::new();
@@ -85,10 +86,39 @@ pub enum AreaKind {
     Desert,
     Swamp,
 }
+impl Display for AreaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "Default"),
+            Self::Forest => write!(f, "Forest"),
+            Self::Desert => write!(f, "Desert"),
+            Self::Swamp => write!(f, "Swamp"),
+        }
+    }
+}
 
 #[derive(Event)]
 pub struct SetArea(pub AreaKind);
 
+pub const STARTING_AREA: AreaKind = AreaKind::Forest;
+
+// RUNS BEFORE THE FIRST BACKGROUNDS ARE ROLLED
+fn set_starting_area(
+    mut current_area: ResMut<CurrentArea>,
+    mut current_area_set: ResMut<CurrentAreaSet>,
+    areas: Res<Areas>,
+) {
+    let Some(area) = current_area_set.set(STARTING_AREA, &areas) else {
+        error!(
+            "[ERROR] Could not set starting Area {}. \nNo Area of that kind.",
+            STARTING_AREA
+        );
+        return;
+    };
+    current_area.0 = area;
+    info!("[MODIFIED] Current Area >> {}", *current_area.title);
+}
+
 pub fn evr_set_area(
     mut evr_set_area: EventReader<SetArea>,
     mut current_area: ResMut<CurrentArea>,
@@ -96,16 +126,14 @@ pub fn evr_set_area(
     areas: Res<Areas>,
 ) {
     for ev in evr_set_area.read() {
-        current_area_set.0.clear();
-        for area in areas.0.iter() {
-            if area.kind == ev.0 {
-                current_area_set.0.push(area.clone());
-            }
-        }
-        current_area.0 = current_area_set
-            .0
-            .first()
-            .expect("[ERROR] Invalid Area Set")
-            .clone();
+        let Some(area) = current_area_set.set(ev.0, &areas) else {
+            error!(
+                "[ERROR] Could not set Area {}. \nNo Area of that kind.",
+                ev.0
+            );
+            continue;
+        };
+        current_area.0 = area;
+        info!("[MODIFIED] Current Area >> {}", *current_area.title);
     }
 }
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+pub struct SoundPlugin;
+impl Plugin for SoundPlugin {
+    fn name(&self) -> &str {
+        "Sound Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_plugins(AudioPlugin)
+            .add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<EffectsChannel>();
+    }
+}
+
+// play music through AudioChannel<MusicChannel>, its volume follows the music setting
+#[derive(Resource)]
+pub struct MusicChannel;
+
+// play sound effects through AudioChannel<EffectsChannel>, its volume follows the effects setting
+#[derive(Resource)]
+pub struct EffectsChannel;
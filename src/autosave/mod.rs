@@ -0,0 +1,198 @@
+use crate::{
+    area::{evr_set_area, SetArea},
+    save::{Save, SaveTask},
+    settings::Settings,
+    theme::{FontRole, Theme},
+    AppState, GameState, RunSet,
+};
+use bevy::prelude::*;
+
+pub struct AutosavePlugin;
+impl Plugin for AutosavePlugin {
+    fn name(&self) -> &str {
+        "Autosave Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AutosaveTimer>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                reset_autosave_timer.in_set(RunSet::Reset),
+            )
+            .add_systems(OnEnter(AppState::Playing), spawn_save_indicator)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Combat,
+                    entered: GameState::Home,
+                },
+                autosave,
+            )
+            .add_systems(
+                Update,
+                (
+                    tick_autosave,
+                    // THE SNAPSHOT HAS TO SEE THE AREA THAT WAS JUST ENTERED
+                    autosave_on_area.after(evr_set_area),
+                    update_save_indicator,
+                )
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(OnExit(AppState::Playing), despawn_save_indicator);
+    }
+}
+
+// SECONDS THE INDICATOR STAYS UP, EVEN IF THE WRITE FINISHES SOONER
+pub const SAVE_INDICATOR_DURATION: f32 = 1.;
+
+// None when the interval in settings is 0
+#[derive(Resource, Default)]
+pub struct AutosaveTimer(pub Option<Timer>);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct SaveIndicator(pub Timer);
+
+fn reset_autosave_timer(mut autosave_timer: ResMut<AutosaveTimer>, settings: Res<Settings>) {
+    autosave_timer.0 = (settings.autosave_interval > 0.)
+        .then(|| Timer::from_seconds(settings.autosave_interval, TimerMode::Repeating));
+}
+
+fn tick_autosave(
+    time: Res<Time>,
+    mut autosave_timer: ResMut<AutosaveTimer>,
+    mut evw_save: EventWriter<Save>,
+) {
+    let Some(timer) = autosave_timer.0.as_mut() else {
+        return;
+    };
+    if timer.tick(time.delta()).just_finished() {
+        evw_save.send(Save);
+        info!("[EVENT] [WRITE] Save Game (Timer)");
+    }
+}
+
+fn autosave_on_area(mut evr_set_area: EventReader<SetArea>, mut evw_save: EventWriter<Save>) {
+    if evr_set_area.read().count() > 0 {
+        evw_save.send(Save);
+        info!("[EVENT] [WRITE] Save Game (Area)");
+    }
+}
+
+fn autosave(mut evw_save: EventWriter<Save>) {
+    evw_save.send(Save);
+    info!("[EVENT] [WRITE] Save Game (Combat)");
+}
+
+fn spawn_save_indicator(mut commands: Commands, theme: Res<Theme>) {
+    let mut timer = Timer::from_seconds(SAVE_INDICATOR_DURATION, TimerMode::Once);
+    timer.tick(timer.duration());
+    commands.spawn((
+        Text::new("SAVING..."),
+        theme.text(FontRole::Small),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.),
+            right: Val::Px(10.),
+            ..default()
+        },
+        Visibility::Hidden,
+        SaveIndicator(timer),
+    ));
+}
+
+// SHOWN WHILE A WRITE IS IN FLIGHT, KEPT UP LONG ENOUGH TO BE READ
+fn update_save_indicator(
+    time: Res<Time>,
+    save_task: Res<SaveTask>,
+    mut query_indicator: Query<(&mut SaveIndicator, &mut Visibility)>,
+) {
+    for (mut timer, mut visibility) in query_indicator.iter_mut() {
+        if save_task.is_busy() {
+            timer.reset();
+        } else {
+            timer.tick(time.delta());
+        }
+        let target = match timer.finished() {
+            true => Visibility::Hidden,
+            false => Visibility::Inherited,
+        };
+        visibility.set_if_neq(target);
+    }
+}
+
+fn despawn_save_indicator(
+    mut commands: Commands,
+    query_indicator: Query<Entity, With<SaveIndicator>>,
+) {
+    for entity in query_indicator.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::{Area, AreaKind, Areas, CurrentArea, CurrentAreaSet};
+    use bevy::state::app::StatesPlugin;
+
+    // AREA KIND CURRENT WHEN EACH SAVE WAS SEEN
+    #[derive(Resource, Default)]
+    struct Snapshots(Vec<AreaKind>);
+
+    fn snapshot(
+        mut evr_save: EventReader<Save>,
+        current_area: Res<CurrentArea>,
+        mut snapshots: ResMut<Snapshots>,
+    ) {
+        for _ in evr_save.read() {
+            snapshots.0.push(current_area.kind);
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_state::<AppState>()
+            .add_sub_state::<GameState>()
+            .add_event::<Save>()
+            .add_event::<SetArea>()
+            .init_resource::<Theme>()
+            .init_resource::<Settings>()
+            .init_resource::<SaveTask>()
+            .init_resource::<CurrentArea>()
+            .init_resource::<CurrentAreaSet>()
+            .init_resource::<Snapshots>()
+            .insert_resource(Areas(vec![Area {
+                kind: AreaKind::Desert,
+                ..default()
+            }]))
+            .add_plugins(AutosavePlugin)
+            .add_systems(
+                Update,
+                (evr_set_area, snapshot.after(autosave_on_area))
+                    .run_if(in_state(AppState::Playing)),
+            );
+        app.world_mut()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Playing);
+        app.update();
+        app
+    }
+
+    #[test]
+    fn entering_an_area_saves_it() {
+        let mut app = app();
+        app.world_mut().send_event(SetArea(AreaKind::Desert));
+        app.update();
+        let snapshots = &app.world().resource::<Snapshots>().0;
+        assert_eq!(snapshots.len(), 1);
+        assert!(snapshots[0] == AreaKind::Desert);
+    }
+
+    #[test]
+    fn no_area_no_save() {
+        let mut app = app();
+        app.update();
+        assert!(app.world().resource::<Snapshots>().0.is_empty());
+    }
+}
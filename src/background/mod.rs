@@ -1,9 +1,12 @@
 use crate::{
     area::{AreaKind, CurrentArea},
+    chance::{ChanceKind, GameRng},
     loading::BackgroundAssets,
-    AppState, SpawnLocations,
+    AppState, GameState, PauseState, SpawnLocations, BACKGROUND_SCALE, DISTANCE_SCALE,
+    SCROLL_SPEED,
 };
 use bevy::prelude::*;
+use rand::Rng;
 
 pub struct BackgroundPlugin;
 
@@ -13,9 +16,15 @@ impl Plugin for BackgroundPlugin {
             .add_systems(OnEnter(AppState::Playing), spawn_initial_backgrounds)
             .add_systems(
                 Update,
-                (move_backgrounds, evr_spawn_background, despawn_background)
-                    .run_if(in_state(AppState::Playing)),
-            );
+                evr_spawn_background.run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (move_backgrounds, despawn_background)
+                    .run_if(in_state(GameState::Home))
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_systems(OnExit(AppState::Playing), despawn_backgrounds);
     }
 }
 
@@ -48,39 +57,55 @@ pub fn evr_spawn_background(
     spawn_locations: Res<SpawnLocations>,
     background_assets: Res<BackgroundAssets>,
     current_area: Res<CurrentArea>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
-    //areas: Res<Areas>,
 ) {
     for ev in evr_spawn_background.read() {
         use AreaKind::*;
 
-        let texture = match current_area.0.kind {
-            Forest => background_assets.background_forest_atlas.clone(),
-            _ => background_assets.background_forest_atlas.clone(), // TODO: CREATE DEFAULT BG
+        let (texture, layout) = match current_area.0.kind {
+            Forest => (
+                background_assets.background_forest_atlas.clone(),
+                background_assets.background_forest_layout.clone(),
+            ),
+            _ => (
+                background_assets.background_forest_atlas.clone(), // TODO: CREATE DEFAULT BG
+                background_assets.background_forest_layout.clone(),
+            ),
+        };
+        // ATLAS INDEX IS ROLLED FROM THE AREA'S BACKGROUND WEIGHTING
+        // AN EMPTY OR FAILED ROLL FALLS BACK TO ANY TILE OF THE ATLAS
+        let index = match current_area.weighting_bg.weigh(&mut rng) {
+            Some(index) => index,
+            None => {
+                warn_once!(
+                    "[FALLBACK] Background: no roll for {}, picking uniformly",
+                    *current_area.title
+                );
+                let count = layouts
+                    .get(&layout)
+                    .map_or(1, |layout| layout.textures.len().max(1));
+                rng.stream(ChanceKind::BACKGROUND).gen_range(0..count)
+            }
         };
 
-        /* commands.spawn((
-            SpriteBundle {
-                texture: match current_area.0.kind {
-                    Forest => background_assets.background_forest_0.clone(),
-                    _ => background_assets.background_forest_1.clone(),
-                },
-                transform: Transform {
-                    translation: spawn_locations.backgrounds[**ev],
-                    scale: Vec3::splat(BACKGROUND_SCALE),
-                    ..default()
-                },
+        commands.spawn((
+            Sprite::from_atlas_image(texture, TextureAtlas { layout, index }),
+            Transform {
+                translation: spawn_locations.backgrounds[**ev],
+                scale: Vec3::splat(BACKGROUND_SCALE),
                 ..default()
             },
             Background,
-        )); */
-        info!("[EVENT] [READ] SpawnBackground({})", **ev);
+        ));
+        info!("[EVENT] [READ] SpawnBackground({}) >> {}", **ev, index);
     }
 }
 
 pub fn move_backgrounds(mut query_background: Query<(&Background, &mut Transform)>) {
     for (_bg, mut tf) in query_background.iter_mut() {
-        tf.translation.x -= 1.;
+        tf.translation.x -= SCROLL_SPEED * DISTANCE_SCALE;
     }
 }
 
@@ -99,3 +124,13 @@ pub fn despawn_background(
         }
     }
 }
+
+pub fn despawn_backgrounds(
+    mut commands: Commands,
+    query_background: Query<Entity, With<Background>>,
+) {
+    for entity in query_background.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("[DESPAWNED] [ENTITY] Backgrounds");
+}
@@ -0,0 +1,101 @@
+use crate::VIRTUAL_RESOLUTION;
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{ScalingMode, Viewport},
+        view::RenderLayers,
+    },
+    ui::IsDefaultUiCamera,
+    window::{PrimaryWindow, WindowResized},
+};
+
+pub struct CameraPlugin;
+impl Plugin for CameraPlugin {
+    fn name(&self) -> &str {
+        "Camera Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_cameras)
+            .add_systems(Update, fit_world_camera);
+    }
+}
+
+// renders sprites at VIRTUAL_RESOLUTION, upscaled by a whole number and letterboxed
+#[derive(Component, Clone, Copy)]
+pub struct WorldCamera;
+
+// renders the ui over the full window so menus aren't squeezed into the letterbox
+#[derive(Component, Clone, Copy)]
+pub struct UiCamera;
+
+// NOTHING IS ON THIS LAYER, IT ONLY KEEPS SPRITES OUT OF THE UI CAMERA
+const UI_LAYER: usize = 1;
+
+fn spawn_cameras(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: VIRTUAL_RESOLUTION.x,
+                height: VIRTUAL_RESOLUTION.y,
+            },
+            ..OrthographicProjection::default_2d()
+        },
+        WorldCamera,
+    ));
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        RenderLayers::layer(UI_LAYER),
+        IsDefaultUiCamera,
+        UiCamera,
+    ));
+    info!("[SPAWNED] Cameras");
+}
+
+// THE LARGEST WHOLE SCALE THAT FITS, CENTERED, THE CLEAR COLOR FILLS THE BARS
+fn fit_world_camera(
+    mut evr_window_resized: EventReader<WindowResized>,
+    query_window: Query<&Window, With<PrimaryWindow>>,
+    mut query_camera: Query<&mut Camera, With<WorldCamera>>,
+) {
+    if evr_window_resized.read().last().is_none() {
+        return;
+    }
+    let (Ok(window), Ok(mut camera)) = (query_window.get_single(), query_camera.get_single_mut())
+    else {
+        return;
+    };
+    let window_size = window.physical_size();
+    // MINIMIZED, A ZERO SIZED VIEWPORT WOULD FAIL TO RENDER
+    if window_size.min_element() == 0 {
+        return;
+    }
+    let virtual_size = VIRTUAL_RESOLUTION.as_uvec2();
+    // A WINDOW SMALLER THAN THE VIRTUAL RESOLUTION GETS A FRACTIONAL DOWNSCALE INSTEAD
+    let scale = (window_size / virtual_size).min_element().max(1);
+    let size = (virtual_size * scale).min(window_size);
+    let viewport = Viewport {
+        physical_position: (window_size - size) / 2,
+        physical_size: size,
+        ..default()
+    };
+    if camera
+        .viewport
+        .as_ref()
+        .map(|current| (current.physical_position, current.physical_size))
+        == Some((viewport.physical_position, viewport.physical_size))
+    {
+        return;
+    }
+    camera.viewport = Some(viewport);
+    info!(
+        "[MODIFIED] World Camera : {}x scale, {}x{} viewport",
+        scale, size.x, size.y
+    );
+}
@@ -1,6 +1,9 @@
+use crate::{settings::Settings, AppState, RunSet};
 use bevy::{prelude::*, utils::hashbrown::HashMap};
 use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
 
 pub struct ChancePlugin;
 impl Plugin for ChancePlugin {
@@ -9,23 +12,109 @@ impl Plugin for ChancePlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app;
+        app.init_resource::<GameRng>()
+            .add_systems(OnEnter(AppState::Playing), seed_run.in_set(RunSet::Reset));
     }
 }
 
+// ONE SEED PER RUN, SPLIT INTO AN INDEPENDENT STREAM PER CHANCEKIND
+// SO E.G. EXTRA LOOT ROLLS NEVER SHIFT THE ENEMY SEQUENCE
+// CHACHA12 IS WHAT StdRng WRAPS, USED DIRECTLY SO A STREAM'S POSITION CAN BE SAVED
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<ChanceKind, ChaCha12Rng>,
+}
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(thread_rng().gen())
+    }
+}
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    // PICKS A SAVED RUN BACK UP WHERE EACH STREAM LEFT OFF, MISSING STREAMS START FRESH
+    pub fn resume(&mut self, seed: u64, positions: &HashMap<ChanceKind, u64>) {
+        self.reseed(seed);
+        for (kind, position) in positions.iter() {
+            self.stream(*kind).set_word_pos(*position as u128);
+        }
+    }
+
+    // HOW FAR EACH STREAM HAS BEEN DRAWN, IN 32 BIT WORDS
+    pub fn positions(&self) -> HashMap<ChanceKind, u64> {
+        self.streams
+            .iter()
+            .map(|(kind, stream)| {
+                let position = u64::try_from(stream.get_word_pos()).unwrap_or(u64::MAX);
+                (*kind, position)
+            })
+            .collect()
+    }
+
+    pub fn stream(&mut self, kind: ChanceKind) -> &mut ChaCha12Rng {
+        let seed = self.seed ^ kind.salt();
+        self.streams
+            .entry(kind)
+            .or_insert_with(|| ChaCha12Rng::seed_from_u64(seed))
+    }
+}
+
+// --seed <u64> OR --seed=<u64>
+pub fn seed_from_args() -> Option<u64> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            return args.next().and_then(|seed| seed.parse().ok());
+        }
+        if let Some(seed) = arg.strip_prefix("--seed=") {
+            return seed.parse().ok();
+        }
+    }
+    None
+}
+
+// COMMAND LINE TAKES PRIORITY OVER SETTINGS, OTHERWISE EVERY RUN IS FRESH
+fn seed_run(mut rng: ResMut<GameRng>, settings: Res<Settings>) {
+    let seed = seed_from_args()
+        .or(settings.seed)
+        .unwrap_or_else(|| thread_rng().gen());
+    rng.reseed(seed);
+    info!("[INITIALIZED] Run Seed: {}", seed);
+}
+
 #[derive(Clone, Copy, Default, Deserialize, Serialize)]
 pub struct Chance {
     pub success: f32,
     pub failure: f32,
 }
 
-#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[derive(Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, Hash, Debug)]
 pub enum ChanceKind {
     #[default]
     DEFAULT,
     LOOT,
     ENEMY,
     BACKGROUND,
+    COMBAT,
+}
+impl ChanceKind {
+    fn salt(&self) -> u64 {
+        (*self as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
 }
 
 #[derive(Clone, Default, Deserialize, Serialize)]
@@ -35,7 +124,11 @@ pub struct Weightings(pub Vec<Weighting>);
 pub struct Weighting {
     pub kind: ChanceKind,
     pub chance: Chance,
-    pub pool: HashMap<usize, f32>,
+    // private so it can't change under the alias table built from it, set through new
+    pool: HashMap<usize, f32>,
+    // built from pool on first roll
+    #[serde(skip)]
+    alias: OnceLock<Option<AliasTable>>,
 }
 impl Weighting {
     pub fn new(success: f32, failure: f32, kind: ChanceKind, pool: HashMap<usize, f32>) -> Self {
@@ -43,51 +136,221 @@ impl Weighting {
             kind,
             chance: Chance { success, failure },
             pool,
+            alias: OnceLock::new(),
+        }
+    }
+
+    pub fn pool(&self) -> &HashMap<usize, f32> {
+        &self.pool
+    }
+
+    // Rolls the success/failure gate first, then picks a pool entry
+    // with probability proportional to its weight
+    pub fn weigh(&self, rng: &mut GameRng) -> Option<usize> {
+        let rng = rng.stream(self.kind);
+        if !self.chance.roll(rng) {
+            return None;
+        }
+        self.alias
+            .get_or_init(|| AliasTable::new(&self.pool))
+            .as_ref()
+            .map(|table| table.sample(rng))
+    }
+}
+impl Chance {
+    // success / (success + failure), a zeroed chance never succeeds
+    pub fn probability(&self) -> f32 {
+        let success = self.success.max(0.);
+        let total = success + self.failure.max(0.);
+        if total > 0. {
+            success / total
+        } else {
+            0.
         }
     }
 
-    pub fn weigh(&self) -> Option<usize> {
-        // Set min/max values for normalization
-        let min: f32 = self
-            .pool
-            .values()
-            .copied()
-            .fold(f32::INFINITY, |x, y| x.min(y));
-        let max: f32 = self
-            .pool
-            .values()
-            .copied()
-            .fold(f32::NEG_INFINITY, |x, y| x.max(y));
-
-        // Clone the hashmap and normalize its chance values
-        // once I determine if pools are immutable or not
-        // I can just mutate the original if necessary
-        let normalized: HashMap<usize, f32> = self
-            .pool
+    pub fn roll(&self, rng: &mut impl Rng) -> bool {
+        rng.gen::<f32>() < self.probability()
+    }
+}
+
+// Vose's alias method: O(n) to build, O(1) per sample
+#[derive(Clone)]
+pub struct AliasTable {
+    keys: Vec<usize>,
+    prob: Vec<f32>,
+    alias: Vec<usize>,
+}
+impl AliasTable {
+    // None when the pool has no positive weight to pick from
+    pub fn new(pool: &HashMap<usize, f32>) -> Option<Self> {
+        // Sorted so the same pool always builds the same table
+        let mut entries: Vec<(usize, f32)> = pool
             .iter()
-            .map(|(k, &v)| {
-                let norm = (v - min) / (max - min);
-                (k.clone(), norm)
-            })
+            .filter(|(_, &w)| w.is_finite() && w > 0.)
+            .map(|(&k, &w)| (k, w))
             .collect();
+        entries.sort_by_key(|(k, _)| *k);
 
-        // Spawn rng thread and gen an f32 from 0 to 1
-        let mut rng = thread_rng();
-        let gen = rng.gen_range(0.0..=1.0);
+        let n = entries.len();
+        let total: f32 = entries.iter().map(|(_, w)| w).sum();
+        if n == 0 || total <= 0. {
+            return None;
+        }
 
-        // Cumulative probability to weigh against
-        let mut prob: f32 = 0.0;
+        let keys: Vec<usize> = entries.iter().map(|(k, _)| *k).collect();
+        let mut scaled: Vec<f32> = entries.iter().map(|(_, w)| w * n as f32 / total).collect();
+        let mut prob = vec![1.; n];
+        let mut alias: Vec<usize> = (0..n).collect();
 
-        // Find the first entry where
-        let choice: Option<usize> = normalized.iter().find_map(|(k, &v)| {
-            prob += v;
-            if gen < prob {
-                Some(k.clone())
-            } else {
-                None
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| scaled[i] < 1.);
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            prob[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] -= 1. - scaled[s];
+            if scaled[l] < 1. {
+                large.pop();
+                small.push(l);
             }
-        });
+        }
+        // Leftovers are 1.0 up to float error
+
+        Some(Self { keys, prob, alias })
+    }
+
+    pub fn sample(&self, rng: &mut impl Rng) -> usize {
+        let i = rng.gen_range(0..self.keys.len());
+        if rng.gen::<f32>() < self.prob[i] {
+            self.keys[i]
+        } else {
+            self.keys[self.alias[i]]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: usize = 100_000;
+    const TOLERANCE: f32 = 0.01;
+    const SEED: u64 = 0x5EED;
+
+    fn pool(entries: &[(usize, f32)]) -> HashMap<usize, f32> {
+        entries.iter().copied().collect()
+    }
+
+    // FRACTION OF ROLLS THAT LANDED ON EACH KEY, MISSES UNDER None
+    fn frequencies(weighting: &Weighting) -> HashMap<Option<usize>, f32> {
+        let mut rng = GameRng::new(SEED);
+        let mut counts: HashMap<Option<usize>, usize> = HashMap::new();
+        for _ in 0..SAMPLES {
+            *counts.entry(weighting.weigh(&mut rng)).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(key, count)| (key, count as f32 / SAMPLES as f32))
+            .collect()
+    }
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < TOLERANCE,
+            "{} not within {} of {}",
+            actual,
+            TOLERANCE,
+            expected
+        );
+    }
+
+    #[test]
+    fn uneven_pool_matches_weights() {
+        let weighting = Weighting::new(1., 0., ChanceKind::LOOT, pool(&[(1, 0.7), (2, 0.3)]));
+        let frequencies = frequencies(&weighting);
+        assert_near(frequencies[&Some(1)], 0.7);
+        assert_near(frequencies[&Some(2)], 0.3);
+        assert!(!frequencies.contains_key(&None));
+    }
+
+    #[test]
+    fn unnormalised_pool_matches_weights() {
+        let weighting = Weighting::new(
+            1.,
+            0.,
+            ChanceKind::ENEMY,
+            pool(&[(0, 1.), (1, 2.), (2, 3.), (3, 4.)]),
+        );
+        let frequencies = frequencies(&weighting);
+        for key in 0..4 {
+            assert_near(frequencies[&Some(key)], (key + 1) as f32 / 10.);
+        }
+    }
+
+    #[test]
+    fn single_entry_always_picked() {
+        let weighting = Weighting::new(1., 0., ChanceKind::LOOT, pool(&[(7, 0.2)]));
+        let frequencies = frequencies(&weighting);
+        assert_eq!(frequencies.len(), 1);
+        assert_eq!(frequencies[&Some(7)], 1.);
+    }
+
+    #[test]
+    fn invalid_weights_are_filtered() {
+        let weighting = Weighting::new(
+            1.,
+            0.,
+            ChanceKind::LOOT,
+            pool(&[
+                (1, 1.),
+                (2, 0.),
+                (3, f32::NAN),
+                (4, -1.),
+                (5, f32::INFINITY),
+            ]),
+        );
+        let frequencies = frequencies(&weighting);
+        assert_eq!(frequencies.len(), 1);
+        assert_eq!(frequencies[&Some(1)], 1.);
+        assert!(AliasTable::new(&pool(&[(2, 0.), (3, f32::NAN)])).is_none());
+    }
+
+    #[test]
+    fn empty_pool_gives_none() {
+        let weighting = Weighting::new(1., 0., ChanceKind::BACKGROUND, HashMap::new());
+        let mut rng = GameRng::new(SEED);
+        assert!((0..100).all(|_| weighting.weigh(&mut rng).is_none()));
+    }
+
+    #[test]
+    fn chance_gates_the_roll() {
+        let weighting = Weighting::new(0.3, 0.7, ChanceKind::ENEMY, pool(&[(1, 1.)]));
+        let frequencies = frequencies(&weighting);
+        assert_near(frequencies[&Some(1)], 0.3);
+        assert_near(frequencies[&None], 0.7);
+    }
+
+    #[test]
+    fn resumed_streams_continue_the_sequence() {
+        let mut rng = GameRng::new(SEED);
+        for _ in 0..37 {
+            rng.stream(ChanceKind::LOOT).gen::<u32>();
+        }
+        rng.stream(ChanceKind::ENEMY).gen::<f32>();
+        let mut resumed = GameRng::new(0);
+        resumed.resume(rng.seed(), &rng.positions());
+        for kind in [ChanceKind::LOOT, ChanceKind::ENEMY, ChanceKind::COMBAT] {
+            let expected: Vec<u64> = (0..8).map(|_| rng.stream(kind).gen()).collect();
+            let actual: Vec<u64> = (0..8).map(|_| resumed.stream(kind).gen()).collect();
+            assert_eq!(actual, expected);
+        }
+    }
 
-        choice
+    #[test]
+    fn zeroed_chance_never_succeeds() {
+        let chance = Chance::default();
+        assert_eq!(chance.probability(), 0.);
+        let mut rng = GameRng::new(SEED);
+        assert!((0..100).all(|_| !chance.roll(rng.stream(ChanceKind::DEFAULT))));
     }
 }
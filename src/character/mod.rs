@@ -1,36 +1,18 @@
-use crate::{weapon::WeaponID, Title, ID};
-use bevy::prelude::*;
-use ron::de::from_reader;
+use crate::{loading::RonAssetPlugin, weapon::WeaponID, Title, ID};
+use bevy::{ecs::query::QueryData, prelude::*};
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, fs::File};
+use std::fmt::Display;
 
 pub struct CharacterPlugin;
 
 impl Plugin for CharacterPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Characters::init());
+        app.add_plugins(RonAssetPlugin::<Characters>::new(&["characters.ron"]));
     }
 }
 
-#[derive(Resource, Clone, Default, Deserialize, Serialize)]
+#[derive(Asset, TypePath, Resource, Clone, Default, Deserialize, Serialize)]
 pub struct Characters(pub Vec<CharacterBundle>);
-impl Characters {
-    fn init() -> Self {
-        let input_path = format!("{}/ron/characters.ron", env!("CARGO_MANIFEST_DIR"));
-        let f = File::open(input_path.clone()).expect("Failed opening file");
-        let characters: Characters = match from_reader(f) {
-            Ok(x) => {
-                info!("[INITIALIZED] Characters: {}", x);
-                x
-            }
-            Err(e) => {
-                eprintln!("[ERROR] Could not deserialize {}. \n{}", input_path, e);
-                Self::default()
-            }
-        };
-        characters
-    }
-}
 impl Display for Characters {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut string: String = String::new();
@@ -73,6 +55,36 @@ impl Default for CharacterBundle {
     }
 }
 
+// READ-ONLY VIEW OF A SPAWNED CHARACTER'S BUNDLE COMPONENTS
+#[derive(QueryData)]
+pub struct CharacterQuery {
+    pub id: &'static CharacterID,
+    pub title: &'static Title,
+    pub constitution: &'static Constitution,
+    pub strength: &'static Strength,
+    pub dexterity: &'static Dexterity,
+    pub intelligence: &'static Intelligence,
+    pub wisdom: &'static Wisdom,
+    pub luck: &'static Luck,
+    pub dwid: &'static WeaponID,
+}
+impl CharacterQueryItem<'_> {
+    pub fn bundle(&self) -> CharacterBundle {
+        CharacterBundle {
+            character: Character,
+            id: *self.id,
+            title: self.title.clone(),
+            constitution: *self.constitution,
+            strength: *self.strength,
+            dexterity: *self.dexterity,
+            intelligence: *self.intelligence,
+            wisdom: *self.wisdom,
+            luck: *self.luck,
+            dwid: *self.dwid,
+        }
+    }
+}
+
 #[derive(Component, Clone, Copy, Deserialize, Serialize)]
 pub struct Character;
 #[derive(Component, Clone, Copy, Default, Deserialize, Serialize)]
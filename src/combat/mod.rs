@@ -0,0 +1,452 @@
+use crate::{
+    chance::{ChanceKind, GameRng},
+    character::CharacterQuery,
+    damage::{DamageBreakdown, DamageCoefficients},
+    encounter::{Encounter, EncounterKind},
+    enemy::{Enemies, EnemyID},
+    equipment::Equipment,
+    health::{Died, Health, TakeDamage},
+    hud::HudMessage,
+    item::{CollectItem, LootTables},
+    player::{Lane, Player, PlayerKills},
+    weapon::{Weapon, Weapons},
+    AppState, GameState, PauseState, SpawnLocations, Title,
+};
+use bevy::prelude::*;
+use rand::Rng;
+use std::fmt::Display;
+
+pub struct CombatPlugin;
+impl Plugin for CombatPlugin {
+    fn name(&self) -> &str {
+        "Combat Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CombatTurn>()
+            .add_event::<Attack>()
+            .add_systems(
+                Update,
+                engage_combat
+                    .run_if(in_state(GameState::Home))
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_systems(OnEnter(GameState::Combat), start_combat)
+            .add_systems(OnExit(GameState::Combat), end_combat)
+            .add_systems(
+                Update,
+                (take_turn, evr_attack, resolve_combat, break_stalemate)
+                    .chain()
+                    .run_if(in_state(GameState::Combat))
+                    .run_if(in_state(PauseState::Running)),
+            );
+    }
+}
+
+// SECONDS BETWEEN EACH SIDE'S ACTION
+pub const TURN_DURATION: f32 = 1.;
+// TURNS IN A ROW WITHOUT EITHER SIDE DEALING DAMAGE BEFORE THE FIGHT IS CALLED OFF
+pub const STALEMATE_TURNS: u32 = 6;
+
+// attached to the combat encounter currently fighting the player
+#[derive(Component, Clone, Copy)]
+pub struct Opponent;
+
+// attached to the party member currently fighting the opponent
+#[derive(Component, Clone, Copy)]
+pub struct Combatant;
+
+#[derive(Resource)]
+pub struct CombatTurn {
+    pub side: CombatSide,
+    pub timer: Timer,
+    pub idle_turns: u32, // turns since a hit last did damage
+}
+impl Default for CombatTurn {
+    fn default() -> Self {
+        Self {
+            side: CombatSide::default(),
+            timer: Timer::from_seconds(TURN_DURATION, TimerMode::Repeating),
+            idle_turns: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum CombatSide {
+    #[default]
+    Player,
+    Enemy,
+}
+impl CombatSide {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Player => Self::Enemy,
+            Self::Enemy => Self::Player,
+        }
+    }
+}
+impl Display for CombatSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Player => write!(f, "Player"),
+            Self::Enemy => write!(f, "Enemy"),
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct Attack {
+    pub attacker: Entity,
+    pub defender: Entity,
+}
+
+// THE LIVING PARTY MEMBER IN THE GIVEN LANE, OR THE FRONT-MOST ONE LEFT STANDING
+fn pick_combatant<'a>(
+    party: impl Iterator<Item = (Entity, &'a Lane, &'a Health)>,
+    lane: usize,
+) -> Option<Entity> {
+    party
+        .filter(|(_, _, health)| !health.is_dead())
+        .min_by_key(|(_, party_lane, _)| (***party_lane != lane, ***party_lane))
+        .map(|(entity, _, _)| entity)
+}
+
+// STOPS SCROLLING ONCE A COMBAT ENCOUNTER REACHES THE PARTY
+pub fn engage_combat(
+    mut commands: Commands,
+    query_encounter: Query<(Entity, &Encounter, &Transform), Without<Opponent>>,
+    query_party: Query<(Entity, &Lane, &Health), With<Player>>,
+    spawn_locations: Res<SpawnLocations>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (entity, encounter, tf) in query_encounter.iter() {
+        if encounter.kind == EncounterKind::Combat
+            && tf.translation.x <= spawn_locations.characters[0].x
+        {
+            let Some(combatant) = pick_combatant(query_party.iter(), encounter.lane) else {
+                return;
+            };
+            commands.entity(entity).insert(Opponent);
+            commands.entity(combatant).insert(Combatant);
+            next_state.set(GameState::Combat);
+            info!("[MODIFIED] GameState >> Combat");
+            // ONE OPPONENT AT A TIME
+            break;
+        }
+    }
+}
+
+pub fn start_combat(mut combat_turn: ResMut<CombatTurn>) {
+    *combat_turn = CombatTurn::default();
+    info!("[STARTED] Combat");
+}
+
+fn end_combat(mut commands: Commands, query_combatant: Query<Entity, With<Combatant>>) {
+    for entity in query_combatant.iter() {
+        commands.entity(entity).remove::<Combatant>();
+    }
+}
+
+pub fn take_turn(
+    time: Res<Time>,
+    mut combat_turn: ResMut<CombatTurn>,
+    mut evw_attack: EventWriter<Attack>,
+    query_combatant: Query<Entity, (With<Player>, With<Combatant>)>,
+    query_opponent: Query<Entity, With<Opponent>>,
+) {
+    if !combat_turn.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let (Ok(player), Ok(opponent)) = (query_combatant.get_single(), query_opponent.get_single())
+    else {
+        return;
+    };
+    let (attacker, defender) = match combat_turn.side {
+        CombatSide::Player => (player, opponent),
+        CombatSide::Enemy => (opponent, player),
+    };
+    evw_attack.send(Attack { attacker, defender });
+    info!("[EVENT] [WRITE] Attack({})", combat_turn.side);
+    combat_turn.side = combat_turn.side.next();
+    combat_turn.idle_turns += 1;
+}
+
+pub fn evr_attack(
+    mut evr_attack: EventReader<Attack>,
+    mut evw_take_damage: EventWriter<TakeDamage>,
+    query_combatant: Query<(CharacterQuery, Option<&Equipment>)>,
+    weapons: Res<Weapons>,
+    coefficients: Res<DamageCoefficients>,
+    mut rng: ResMut<GameRng>,
+    mut combat_turn: ResMut<CombatTurn>,
+) {
+    for ev in evr_attack.read() {
+        let Ok((attacker, equipment)) = query_combatant.get(ev.attacker) else {
+            continue;
+        };
+        let Ok((defender, _)) = query_combatant.get(ev.defender) else {
+            continue;
+        };
+        // UNARMED WHEN NOTHING IS EQUIPPED
+        let weapon = equipment
+            .and_then(|equipment| equipment.attack_weapon())
+            .and_then(|wid| weapons.get(wid))
+            .cloned()
+            .unwrap_or_else(Weapon::default);
+        let breakdown = DamageBreakdown::calculate(
+            &attacker.bundle(),
+            &weapon,
+            &defender.bundle(),
+            &coefficients,
+            rng.stream(ChanceKind::COMBAT).gen::<f32>(),
+        );
+        if breakdown.total > 0 {
+            combat_turn.idle_turns = 0;
+        }
+        evw_take_damage.send(TakeDamage {
+            target: ev.defender,
+            amount: breakdown.total,
+        });
+        info!(
+            "[EVENT] [READ] Attack: {} hits {} with {} for {}",
+            **attacker.title, **defender.title, *weapon.title, breakdown
+        );
+    }
+}
+
+pub fn resolve_combat(
+    mut commands: Commands,
+    mut evr_died: EventReader<Died>,
+    query_party: Query<(Entity, &Lane, &Health), With<Player>>,
+    query_opponent: Query<(&EnemyID, &Encounter), With<Opponent>>,
+    mut evw_collect_item: EventWriter<CollectItem>,
+    mut player_kills: ResMut<PlayerKills>,
+    mut rng: ResMut<GameRng>,
+    enemies: Res<Enemies>,
+    loot_tables: Res<LootTables>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for ev in evr_died.read() {
+        // THE NEXT PARTY MEMBER STEPS UP, THE RUN ENDS WITH THE LAST ONE
+        if query_party.contains(**ev) {
+            commands.entity(**ev).remove::<Combatant>();
+            let lane = query_opponent
+                .get_single()
+                .map_or(0, |(_, encounter)| encounter.lane);
+            let Some(next) = pick_combatant(query_party.iter(), lane) else {
+                next_app_state.set(AppState::GameOver);
+                info!("[MODIFIED] AppState >> GameOver");
+                return;
+            };
+            commands.entity(next).insert(Combatant);
+            info!("[MODIFIED] Combatant >> {}", next);
+            continue;
+        }
+        if let Ok((id, _)) = query_opponent.get(**ev) {
+            commands.entity(**ev).despawn_recursive();
+            info!("[DESPAWNED] Encounter: Combat");
+            **player_kills += 1;
+            if let Some(table) = enemies
+                .get(*id.0)
+                .and_then(|enemy| enemy.drops.weigh(&mut rng))
+            {
+                for stack in loot_tables.roll(table, &mut rng) {
+                    evw_collect_item.send(CollectItem(stack));
+                }
+                info!("[DROPPED] Loot Table: {}", table);
+            }
+            next_game_state.set(GameState::Home);
+            info!("[MODIFIED] GameState >> Home");
+        }
+    }
+    // OPPONENT IS GONE, NOTHING LEFT TO FIGHT
+    if query_opponent.is_empty() {
+        next_game_state.set(GameState::Home);
+    }
+}
+
+// NEITHER SIDE CAN HURT THE OTHER, E.G. A MINIMUM OF 0 AND TWO SPECIAL WEAPONS
+// THE OPPONENT LEAVES WITHOUT COUNTING AS A KILL OR DROPPING LOOT
+pub fn break_stalemate(
+    mut commands: Commands,
+    combat_turn: Res<CombatTurn>,
+    query_opponent: Query<(Entity, &Title), With<Opponent>>,
+    mut evw_hud_message: EventWriter<HudMessage>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if combat_turn.idle_turns < STALEMATE_TURNS {
+        return;
+    }
+    for (entity, title) in query_opponent.iter() {
+        commands.entity(entity).despawn_recursive();
+        evw_hud_message.send(HudMessage(format!("{} WANDERS OFF", title.to_uppercase())));
+        info!("[DESPAWNED] Encounter: Combat (stalemate)");
+    }
+    next_game_state.set(GameState::Home);
+    info!("[MODIFIED] GameState >> Home");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        character::{CharacterBundle, Level, Strength},
+        damage::WeightCoefficient,
+        health::evr_take_damage,
+    };
+    use bevy::{state::app::StatesPlugin, time::TimeUpdateStrategy};
+    use std::time::Duration;
+
+    // EVERY UPDATE ADVANCES ONE TURN
+    fn app(coefficients: DamageCoefficients) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                TURN_DURATION,
+            )))
+            .init_state::<AppState>()
+            .add_sub_state::<GameState>()
+            .add_sub_state::<PauseState>()
+            .add_event::<TakeDamage>()
+            .add_event::<Died>()
+            .add_event::<CollectItem>()
+            .add_event::<HudMessage>()
+            .insert_resource(GameRng::new(0))
+            .insert_resource(coefficients)
+            .init_resource::<SpawnLocations>()
+            .init_resource::<PlayerKills>()
+            .init_resource::<Weapons>()
+            .init_resource::<Enemies>()
+            .init_resource::<LootTables>()
+            .add_plugins(CombatPlugin)
+            .add_systems(
+                Update,
+                evr_take_damage.after(evr_attack).before(resolve_combat),
+            );
+        app.world_mut()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Playing);
+        app.update();
+        app
+    }
+
+    // UNARMED, EACH HIT DOES THE ATTACKER'S STRENGTH
+    fn coefficients() -> DamageCoefficients {
+        let mut coefficients = DamageCoefficients {
+            strength: 1.,
+            minimum: 1,
+            ..default()
+        };
+        coefficients.weight.default = WeightCoefficient {
+            multiplier: 1.,
+            strength: 1.,
+            dexterity: 0.,
+        };
+        coefficients.hand.default = 1.;
+        coefficients
+    }
+
+    fn fighter(strength: i32, health: i32) -> (CharacterBundle, Health) {
+        let character = CharacterBundle {
+            strength: Strength(Level(strength)),
+            ..default()
+        };
+        (
+            character,
+            Health {
+                current: health,
+                max: health,
+            },
+        )
+    }
+
+    fn spawn_player(app: &mut App, lane: usize, strength: i32, health: i32) -> Entity {
+        app.world_mut()
+            .spawn((fighter(strength, health), Player, Lane(lane)))
+            .id()
+    }
+
+    fn spawn_opponent(app: &mut App, strength: i32, health: i32) -> Entity {
+        let encounter = Encounter {
+            kind: EncounterKind::Combat,
+            id: 0,
+            lane: 0,
+        };
+        app.world_mut()
+            .spawn((
+                fighter(strength, health),
+                encounter,
+                EnemyID::default(),
+                Opponent,
+            ))
+            .id()
+    }
+
+    fn fight(app: &mut App, combatant: Entity) {
+        app.world_mut().entity_mut(combatant).insert(Combatant);
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Combat);
+        app.update();
+        for _ in 0..50 {
+            app.update();
+            if *app.world().resource::<State<AppState>>() != AppState::Playing
+                || *app.world().resource::<State<GameState>>() != GameState::Combat
+            {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn winning_counts_the_kill_and_returns_home() {
+        let mut app = app(coefficients());
+        let player = spawn_player(&mut app, 0, 2, 10);
+        let opponent = spawn_opponent(&mut app, 1, 5);
+        fight(&mut app, player);
+        assert_eq!(*app.world().resource::<State<GameState>>(), GameState::Home);
+        assert!(app.world().get_entity(opponent).is_err());
+        assert_eq!(**app.world().resource::<PlayerKills>(), 1);
+        assert_eq!(app.world().get::<Health>(player).unwrap().current, 8);
+    }
+
+    #[test]
+    fn next_party_member_steps_up() {
+        let mut app = app(coefficients());
+        let front = spawn_player(&mut app, 0, 1, 1);
+        let back = spawn_player(&mut app, 1, 3, 10);
+        spawn_opponent(&mut app, 2, 6);
+        fight(&mut app, front);
+        assert!(app.world().get::<Health>(front).unwrap().is_dead());
+        assert!(!app.world().get::<Health>(back).unwrap().is_dead());
+        assert_eq!(*app.world().resource::<State<GameState>>(), GameState::Home);
+        assert_eq!(**app.world().resource::<PlayerKills>(), 1);
+    }
+
+    #[test]
+    fn losing_the_last_party_member_ends_the_run() {
+        let mut app = app(coefficients());
+        let player = spawn_player(&mut app, 0, 1, 2);
+        spawn_opponent(&mut app, 3, 20);
+        fight(&mut app, player);
+        assert_eq!(
+            *app.world().resource::<State<AppState>>(),
+            AppState::GameOver
+        );
+        assert_eq!(**app.world().resource::<PlayerKills>(), 0);
+    }
+
+    #[test]
+    fn harmless_fight_is_called_off() {
+        let mut app = app(DamageCoefficients::default());
+        let player = spawn_player(&mut app, 0, 1, 5);
+        let opponent = spawn_opponent(&mut app, 1, 5);
+        fight(&mut app, player);
+        assert_eq!(*app.world().resource::<State<GameState>>(), GameState::Home);
+        assert!(app.world().get_entity(opponent).is_err());
+        assert_eq!(**app.world().resource::<PlayerKills>(), 0);
+        assert_eq!(app.world().get::<Health>(player).unwrap().current, 5);
+    }
+}
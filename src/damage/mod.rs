@@ -0,0 +1,311 @@
+use crate::{
+    character::CharacterBundle,
+    loading::RonAssetPlugin,
+    weapon::{Weapon, WeaponHand, WeaponWeight},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub struct DamagePlugin;
+impl Plugin for DamagePlugin {
+    fn name(&self) -> &str {
+        "Damage Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<DamageCoefficients>::new(&["damage.ron"]));
+    }
+}
+
+#[derive(Asset, TypePath, Resource, Clone, Default, Serialize, Deserialize)]
+pub struct DamageCoefficients {
+    pub strength: f32,
+    pub dexterity: f32,
+    pub weight: WeightCoefficients,
+    pub hand: HandCoefficients,
+    pub crit_per_luck: f32,
+    pub crit_multiplier: f32,
+    pub mitigation_per_constitution: f32,
+    pub minimum: i32,
+}
+
+// how much each stat contributes for a given weapon weight
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WeightCoefficient {
+    pub multiplier: f32,
+    pub strength: f32,
+    pub dexterity: f32,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WeightCoefficients {
+    pub default: WeightCoefficient,
+    pub feather: WeightCoefficient,
+    pub light: WeightCoefficient,
+    pub middle: WeightCoefficient,
+    pub heavy: WeightCoefficient,
+    pub ultra: WeightCoefficient,
+}
+impl WeightCoefficients {
+    pub fn get(&self, weight: &WeaponWeight) -> WeightCoefficient {
+        match weight {
+            WeaponWeight::DEFAULT => self.default,
+            WeaponWeight::FEATHER => self.feather,
+            WeaponWeight::LIGHT => self.light,
+            WeaponWeight::MIDDLE => self.middle,
+            WeaponWeight::HEAVY => self.heavy,
+            WeaponWeight::ULTRA => self.ultra,
+        }
+    }
+}
+
+// stat scaling multiplier for a given weapon hand
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct HandCoefficients {
+    pub default: f32,
+    pub one: f32,
+    pub two: f32,
+    pub special: f32,
+}
+impl HandCoefficients {
+    pub fn get(&self, hand: &WeaponHand) -> f32 {
+        match hand {
+            WeaponHand::DEFAULT => self.default,
+            WeaponHand::ONE => self.one,
+            WeaponHand::TWO => self.two,
+            WeaponHand::SPECIAL => self.special,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct DamageBreakdown {
+    pub base: f32,
+    pub scaling: f32,
+    pub weight: f32,
+    pub critical: bool,
+    pub crit: f32,
+    pub mitigation: f32,
+    pub total: i32,
+}
+impl DamageBreakdown {
+    // crit_roll is expected in 0.0..1.0 so callers control the randomness
+    pub fn calculate(
+        attacker: &CharacterBundle,
+        weapon: &Weapon,
+        defender: &CharacterBundle,
+        coefficients: &DamageCoefficients,
+        crit_roll: f32,
+    ) -> Self {
+        let weight = coefficients.weight.get(&weapon.weight);
+        let hand = coefficients.hand.get(&weapon.hand);
+
+        let base = weapon.damage.0 .0 as f32;
+        let strength = attacker.strength.0 .0 as f32 * coefficients.strength * weight.strength;
+        let dexterity = attacker.dexterity.0 .0 as f32 * coefficients.dexterity * weight.dexterity;
+        let scaling = (strength + dexterity) * hand;
+        let weight = (base + scaling) * (weight.multiplier - 1.);
+        let raw = (base + scaling + weight).max(0.);
+
+        let crit_chance = (attacker.luck.0 .0 as f32 * coefficients.crit_per_luck).clamp(0., 1.);
+        let critical = crit_roll < crit_chance;
+        let crit = if critical {
+            raw * (coefficients.crit_multiplier - 1.)
+        } else {
+            0.
+        };
+
+        let mitigation = (defender.constitution.0 .0 as f32
+            * coefficients.mitigation_per_constitution)
+            .clamp(0., raw + crit);
+        let total = ((raw + crit - mitigation).round() as i32).max(coefficients.minimum);
+
+        Self {
+            base,
+            scaling,
+            weight,
+            critical,
+            crit,
+            mitigation,
+            total,
+        }
+    }
+}
+impl Display for DamageBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (base {:.1} + scaling {:.1} + weight {:.1} + crit {:.1} - mitigation {:.1})",
+            self.total, self.base, self.scaling, self.weight, self.crit, self.mitigation
+        )?;
+        if self.critical {
+            write!(f, " CRITICAL")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        character::{Constitution, Dexterity, Level, Luck, Strength},
+        weapon::WeaponDamage,
+        Damage,
+    };
+
+    fn coefficients() -> DamageCoefficients {
+        let flat = WeightCoefficient {
+            multiplier: 1.,
+            strength: 1.,
+            dexterity: 1.,
+        };
+        DamageCoefficients {
+            strength: 1.,
+            dexterity: 0.5,
+            weight: WeightCoefficients {
+                default: flat,
+                feather: flat,
+                light: flat,
+                middle: flat,
+                heavy: WeightCoefficient {
+                    multiplier: 1.5,
+                    strength: 1.,
+                    dexterity: 0.,
+                },
+                ultra: flat,
+            },
+            hand: HandCoefficients {
+                default: 1.,
+                one: 1.,
+                two: 1.5,
+                special: 1.,
+            },
+            crit_per_luck: 0.1,
+            crit_multiplier: 2.,
+            mitigation_per_constitution: 1.,
+            minimum: 1,
+        }
+    }
+
+    fn attacker() -> CharacterBundle {
+        CharacterBundle {
+            strength: Strength(Level(2)),
+            dexterity: Dexterity(Level(2)),
+            luck: Luck(Level(5)),
+            ..default()
+        }
+    }
+
+    fn defender(constitution: i32) -> CharacterBundle {
+        CharacterBundle {
+            constitution: Constitution(Level(constitution)),
+            ..default()
+        }
+    }
+
+    fn weapon(weight: WeaponWeight, hand: WeaponHand) -> Weapon {
+        Weapon {
+            weight,
+            hand,
+            damage: WeaponDamage(Damage(3)),
+            ..Weapon::default()
+        }
+    }
+
+    #[test]
+    fn non_critical_hit() {
+        let breakdown = DamageBreakdown::calculate(
+            &attacker(),
+            &weapon(WeaponWeight::DEFAULT, WeaponHand::DEFAULT),
+            &defender(1),
+            &coefficients(),
+            0.9,
+        );
+        assert!(!breakdown.critical);
+        assert_eq!(breakdown.base, 3.);
+        assert_eq!(breakdown.scaling, 3.);
+        assert_eq!(breakdown.crit, 0.);
+        assert_eq!(breakdown.mitigation, 1.);
+        assert_eq!(breakdown.total, 5);
+    }
+
+    #[test]
+    fn critical_hit_multiplies_raw_damage() {
+        let breakdown = DamageBreakdown::calculate(
+            &attacker(),
+            &weapon(WeaponWeight::DEFAULT, WeaponHand::DEFAULT),
+            &defender(1),
+            &coefficients(),
+            0.1,
+        );
+        assert!(breakdown.critical);
+        assert_eq!(breakdown.crit, 6.);
+        assert_eq!(breakdown.total, 11);
+    }
+
+    #[test]
+    fn no_luck_never_crits() {
+        let attacker = CharacterBundle {
+            luck: Luck(Level(0)),
+            ..attacker()
+        };
+        let breakdown = DamageBreakdown::calculate(
+            &attacker,
+            &weapon(WeaponWeight::DEFAULT, WeaponHand::DEFAULT),
+            &defender(0),
+            &coefficients(),
+            0.,
+        );
+        assert!(!breakdown.critical);
+    }
+
+    #[test]
+    fn mitigation_is_floored_at_the_minimum() {
+        let breakdown = DamageBreakdown::calculate(
+            &attacker(),
+            &weapon(WeaponWeight::DEFAULT, WeaponHand::DEFAULT),
+            &defender(100),
+            &coefficients(),
+            0.9,
+        );
+        assert_eq!(breakdown.mitigation, 6.);
+        assert_eq!(breakdown.total, coefficients().minimum);
+    }
+
+    #[test]
+    fn harmless_weapon_still_lands_the_minimum() {
+        // E.G. THE PACIFIER: SPECIAL HANDS GAIN NO SCALING AND THE WEAPON DOES NOTHING
+        let coefficients = DamageCoefficients {
+            hand: HandCoefficients {
+                special: 0.,
+                ..coefficients().hand
+            },
+            ..coefficients()
+        };
+        let pacifier = Weapon {
+            damage: WeaponDamage(Damage(0)),
+            ..weapon(WeaponWeight::FEATHER, WeaponHand::SPECIAL)
+        };
+        let breakdown =
+            DamageBreakdown::calculate(&attacker(), &pacifier, &defender(0), &coefficients, 0.9);
+        assert_eq!(breakdown.scaling, 0.);
+        assert_eq!(breakdown.total, 1);
+    }
+
+    #[test]
+    fn weapon_weight_and_hand_contribute() {
+        let breakdown = DamageBreakdown::calculate(
+            &attacker(),
+            &weapon(WeaponWeight::HEAVY, WeaponHand::TWO),
+            &defender(1),
+            &coefficients(),
+            0.9,
+        );
+        // HEAVY IGNORES DEXTERITY, TWO HANDS SCALE STRENGTH BY 1.5
+        assert_eq!(breakdown.scaling, 3.);
+        assert_eq!(breakdown.weight, 3.);
+        assert_eq!(breakdown.total, 8);
+    }
+}
@@ -1,10 +1,18 @@
 use crate::{
-    character::Character,
+    area::CurrentArea,
+    chance::GameRng,
+    enemy::Enemies,
+    health::Health,
+    item::{CollectItem, ItemStack, Items, LootTables},
+    loading::RonAssetPlugin,
     loading::{CharacterAssets, ItemAssets},
-    player::PlayerLoot,
-    AppState, SpawnLocations, CHARACTER_LAYER, CHARACTER_SCALE, ENCOUNTER_LAYER, ENCOUNTER_SCALE,
+    player::PlayerDistance,
+    AppState, GameState, PauseState, RunSet, SpawnLocations, CHARACTER_LAYER, CHARACTER_SCALE,
+    DISTANCE_SCALE, ENCOUNTER_LAYER, ENCOUNTER_SCALE, SCROLL_SPEED,
 };
 use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 pub struct EncounterPlugin;
@@ -14,39 +22,53 @@ impl Plugin for EncounterPlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app.add_event::<SpawnEncounter>().add_systems(
+        app.add_plugins(RonAssetPlugin::<EncounterSchedule>::new(&[
+            "encounter_schedule.ron",
+        ]))
+        .add_event::<SpawnEncounter>()
+        .add_systems(
+            OnEnter(AppState::Playing),
+            reset_encounter_schedule.in_set(RunSet::Reset),
+        )
+        .add_systems(
             Update,
-            (move_encounter, evr_spawn_encounter, collect_loot).run_if(in_state(AppState::Playing)),
-        );
+            evr_spawn_encounter.run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(
+            Update,
+            (schedule_encounters, move_encounter, collect_loot)
+                .run_if(in_state(GameState::Home))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(OnExit(AppState::Playing), despawn_encounters);
     }
 }
 
 #[derive(Component, Clone, Copy, PartialEq)]
 pub struct Encounter {
     pub kind: EncounterKind,
+    pub id: usize,
+    pub lane: usize,
 }
 
-#[derive(Component, Clone, Copy, PartialEq)]
+#[derive(Component, Clone)]
 pub struct Loot {
-    pub kind: LootKind,
+    pub drops: Vec<ItemStack>,
 }
 
 #[derive(Event)]
 pub struct SpawnEncounter {
     pub kind: EncounterKind,
     pub lane: usize,
+    pub id: usize, // enemy or loot ID rolled from the area's weighting pool
 }
 
-#[derive(Clone, Copy, PartialEq)]
-pub enum LootKind {
-    Money,
-}
-impl Display for LootKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Money => write!(f, "Loot"),
-        }
-    }
+#[derive(Asset, TypePath, Resource, Clone, Default, Serialize, Deserialize)]
+pub struct EncounterSchedule {
+    pub cadence: f32,
+    pub lane_spacing: f32,
+    #[serde(skip)]
+    pub next_roll: f32,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -65,64 +87,146 @@ impl Display for EncounterKind {
 
 pub fn evr_spawn_encounter(
     mut commands: Commands,
+    mut evr_spawn_encounter: EventReader<SpawnEncounter>,
+    mut rng: ResMut<GameRng>,
     spawn_locations: Res<SpawnLocations>,
     character_assets: Res<CharacterAssets>,
     item_assets: Res<ItemAssets>,
-    mut evr_spawn_encounter: EventReader<SpawnEncounter>,
+    enemies: Res<Enemies>,
+    items: Res<Items>,
+    loot_tables: Res<LootTables>,
 ) {
     for ev in evr_spawn_encounter.read() {
-        let x = spawn_locations.encounters[ev.lane].x;
-        let (texture, scale, z) = match ev.kind {
-            EncounterKind::Combat => (
-                character_assets.character_old_man_0.clone(),
-                CHARACTER_SCALE,
-                CHARACTER_LAYER,
-            ),
-            EncounterKind::Loot => (
-                item_assets.item_money_0.clone(),
-                ENCOUNTER_SCALE,
-                ENCOUNTER_LAYER,
-            ),
-        };
-        let transform = Transform {
-            translation: Vec3::new(x, 0., z),
+        let Vec3 { x, y, .. } = spawn_locations.encounters[ev.lane];
+        let transform = |z: f32, scale: f32| Transform {
+            translation: Vec3::new(x, y, z),
             scale: Vec3::splat(scale),
             ..default()
         };
-        let encounter = Encounter { kind: ev.kind };
-        let entity = commands
-            .spawn((Sprite::from_image(texture), transform, encounter))
-            .id();
+        let encounter = Encounter {
+            kind: ev.kind,
+            id: ev.id,
+            lane: ev.lane,
+        };
 
         match ev.kind {
-            EncounterKind::Loot => commands.entity(entity).insert(Loot {
-                kind: LootKind::Money,
-            }),
-            EncounterKind::Combat => commands.entity(entity).insert(Character),
-        };
+            EncounterKind::Combat => {
+                let Some(enemy) = enemies.get(ev.id) else {
+                    warn!("[SKIPPED] Encounter: Combat (unknown enemy {})", ev.id);
+                    continue;
+                };
+                let texture = character_assets.get(&enemy.sprite).unwrap_or_else(|| {
+                    warn!("[MISSING] Sprite: {}", enemy.sprite);
+                    character_assets.character_old_man_0.clone()
+                });
+                let character = enemy.bundle();
+                let health = Health::from_constitution(&character.constitution);
+                commands.spawn((
+                    Sprite::from_image(texture),
+                    transform(CHARACTER_LAYER, CHARACTER_SCALE),
+                    encounter,
+                    character,
+                    health,
+                    enemy.id,
+                ));
+            }
+            EncounterKind::Loot => {
+                // ROLLED UP FRONT SO THE SPRITE SHOWS WHAT WILL BE COLLECTED
+                let drops = loot_tables.roll(ev.id, &mut rng);
+                let Some(first) = drops.first() else {
+                    info!("[SKIPPED] Encounter: Loot (empty roll)");
+                    continue;
+                };
+                let texture = items
+                    .get(first.item)
+                    .and_then(|item| item_assets.get(&item.sprite))
+                    .unwrap_or_else(|| item_assets.item_money_0.clone());
+                commands.spawn((
+                    Sprite::from_image(texture),
+                    transform(ENCOUNTER_LAYER, ENCOUNTER_SCALE),
+                    encounter,
+                    Loot { drops },
+                ));
+            }
+        }
 
         info!("[SPAWNED] Encounter: {}", ev.kind);
     }
 }
 
+fn reset_encounter_schedule(mut schedule: ResMut<EncounterSchedule>) {
+    schedule.next_roll = schedule.cadence;
+}
+
+// ROLLS THE CURRENT AREA'S WEIGHTINGS EVERY `cadence` DISTANCE
+pub fn schedule_encounters(
+    mut schedule: ResMut<EncounterSchedule>,
+    mut evw_spawn_encounter: EventWriter<SpawnEncounter>,
+    mut rng: ResMut<GameRng>,
+    player_distance: Res<PlayerDistance>,
+    current_area: Res<CurrentArea>,
+    spawn_locations: Res<SpawnLocations>,
+    query_encounter: Query<(&Encounter, &Transform)>,
+) {
+    if **player_distance < schedule.next_roll {
+        return;
+    }
+    schedule.next_roll = **player_distance + schedule.cadence;
+
+    // ENEMIES TAKE PRIORITY OVER LOOT
+    let (kind, id, chance_kind) = if let Some(id) = current_area.weighting_enemy.weigh(&mut rng) {
+        (EncounterKind::Combat, id, current_area.weighting_enemy.kind)
+    } else if let Some(id) = current_area.weighting_loot.weigh(&mut rng) {
+        (EncounterKind::Loot, id, current_area.weighting_loot.kind)
+    } else {
+        return;
+    };
+
+    // A LANE IS OPEN ONCE ITS NEWEST ENCOUNTER HAS MOVED `lane_spacing` AWAY
+    let open_lanes: Vec<usize> = (0..spawn_locations.encounters.len())
+        .filter(|&lane| {
+            let spawn_x = spawn_locations.encounters[lane].x;
+            query_encounter
+                .iter()
+                .filter(|(encounter, _)| encounter.lane == lane)
+                .all(|(_, tf)| spawn_x - tf.translation.x >= schedule.lane_spacing * DISTANCE_SCALE)
+        })
+        .collect();
+    let Some(&lane) = open_lanes.choose(rng.stream(chance_kind)) else {
+        info!("[SKIPPED] Encounter: {} (no open lane)", kind);
+        return;
+    };
+
+    evw_spawn_encounter.send(SpawnEncounter { kind, lane, id });
+    info!("[EVENT] [WRITE] SpawnEncounter({}, {}, {})", kind, lane, id);
+}
+
 pub fn move_encounter(mut query_encounter: Query<&mut Transform, With<Encounter>>) {
     for mut tf in query_encounter.iter_mut() {
-        tf.translation.x -= 1.;
+        tf.translation.x -= SCROLL_SPEED * DISTANCE_SCALE;
     }
 }
 
 pub fn collect_loot(
     mut commands: Commands,
     query_encounter: Query<(Entity, &Loot, &Transform)>,
-    mut player_loot: ResMut<PlayerLoot>,
+    mut evw_collect_item: EventWriter<CollectItem>,
     spawn_locations: Res<SpawnLocations>,
 ) {
     for (entity, loot, tf) in query_encounter.iter() {
         if tf.translation.x <= spawn_locations.characters[0].x {
             commands.entity(entity).despawn_recursive();
-            info!("[DESPAWNED] Encounter: {}", loot.kind);
-            **player_loot += 1;
-            info!("[COLLECTED] Loot: {}", loot.kind);
+            info!("[DESPAWNED] Encounter: Loot");
+            for stack in loot.drops.iter() {
+                evw_collect_item.send(CollectItem(*stack));
+            }
         }
     }
 }
+
+pub fn despawn_encounters(mut commands: Commands, query_encounter: Query<Entity, With<Encounter>>) {
+    for entity in query_encounter.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("[DESPAWNED] Encounters");
+}
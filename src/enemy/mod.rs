@@ -0,0 +1,78 @@
+use crate::{
+    chance::Weighting,
+    character::{
+        Character, CharacterBundle, CharacterID, Constitution, Dexterity, Intelligence, Luck,
+        Strength, Wisdom,
+    },
+    loading::RonAssetPlugin,
+    weapon::WeaponID,
+    Title, ID,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub struct EnemyPlugin;
+impl Plugin for EnemyPlugin {
+    fn name(&self) -> &str {
+        "Enemy Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<Enemies>::new(&["enemies.ron"]));
+    }
+}
+
+#[derive(Asset, TypePath, Resource, Clone, Default, Deserialize, Serialize)]
+pub struct Enemies(pub Vec<Enemy>);
+impl Enemies {
+    pub fn get(&self, id: usize) -> Option<&Enemy> {
+        self.0.iter().find(|enemy| *enemy.id.0 == id)
+    }
+}
+impl Display for Enemies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut string: String = String::new();
+        for enemy in self.0.iter() {
+            string.push_str(&enemy.title);
+            string.push_str(&", ");
+        }
+
+        write!(f, "{}", string)
+    }
+}
+
+// ids are the keys of an area's weighting_enemy pool
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Enemy {
+    pub id: EnemyID,
+    pub title: Title,
+    pub constitution: Constitution,
+    pub strength: Strength,
+    pub dexterity: Dexterity,
+    pub intelligence: Intelligence,
+    pub wisdom: Wisdom,
+    pub luck: Luck,
+    pub wid: WeaponID,  // weapon ID
+    pub sprite: String, // CharacterAssets key
+    pub drops: Weighting,
+}
+impl Enemy {
+    pub fn bundle(&self) -> CharacterBundle {
+        CharacterBundle {
+            character: Character,
+            id: CharacterID(ID(*self.id.0)),
+            title: self.title.clone(),
+            constitution: self.constitution,
+            strength: self.strength,
+            dexterity: self.dexterity,
+            intelligence: self.intelligence,
+            wisdom: self.wisdom,
+            luck: self.luck,
+            dwid: self.wid,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Default, Deserialize, Serialize, Deref, DerefMut)]
+pub struct EnemyID(pub ID);
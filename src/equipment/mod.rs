@@ -0,0 +1,390 @@
+use crate::{
+    character::Character,
+    item::{EquipItem, ItemID, ItemStack, Items, StoreItem},
+    player::Player,
+    weapon::{Weapon, WeaponHand, WeaponID, Weapons},
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub struct EquipmentPlugin;
+impl Plugin for EquipmentPlugin {
+    fn name(&self) -> &str {
+        "Equipment Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_event::<EquipWeapon>()
+            .add_event::<UnequipWeapon>()
+            .add_systems(
+                Update,
+                (
+                    equip_default_weapon,
+                    evr_equip_item,
+                    evr_equip_weapon,
+                    evr_unequip_weapon,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+#[derive(Component, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct Equipment {
+    pub main_hand: Option<WeaponID>,
+    pub off_hand: Option<WeaponID>,
+    pub two_handed: bool,        // main hand weapon also occupies the off hand
+    pub armor: Option<ItemID>,   // reserved
+    pub trinket: Option<ItemID>, // reserved
+}
+impl Equipment {
+    // MAIN HAND ATTACKS, OFF HAND ONLY WHEN THE MAIN HAND IS EMPTY
+    pub fn attack_weapon(&self) -> Option<WeaponID> {
+        self.main_hand.or(self.off_hand)
+    }
+
+    // WHERE A WEAPON WOULD GO WITHOUT DISPLACING ANYTHING
+    pub fn free_slot(&self, weapon: &Weapon) -> Option<EquipSlot> {
+        let hands_free = !self.two_handed && self.main_hand.is_none();
+        match weapon.hand {
+            WeaponHand::TWO => {
+                (hands_free && self.off_hand.is_none()).then_some(EquipSlot::MainHand)
+            }
+            WeaponHand::SPECIAL => {
+                (!self.two_handed && self.off_hand.is_none()).then_some(EquipSlot::OffHand)
+            }
+            WeaponHand::ONE | WeaponHand::DEFAULT => {
+                if hands_free {
+                    Some(EquipSlot::MainHand)
+                } else if !self.two_handed && self.off_hand.is_none() {
+                    Some(EquipSlot::OffHand)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    // WHERE A WEAPON GOES WHEN THE PLAYER PICKS IT, DISPLACING WHAT'S THERE IF NOTHING IS FREE
+    pub fn slot_for(&self, weapon: &Weapon) -> EquipSlot {
+        self.free_slot(weapon).unwrap_or(match weapon.hand {
+            WeaponHand::SPECIAL => EquipSlot::OffHand,
+            _ => EquipSlot::MainHand,
+        })
+    }
+
+    // TWO HANDED WEAPONS TAKE BOTH HANDS, SPECIAL WEAPONS ONLY FIT THE OFF HAND
+    // RETURNS WHATEVER HAD TO BE TAKEN OFF TO MAKE ROOM
+    pub fn equip(&mut self, weapon: &Weapon, slot: EquipSlot) -> Result<Vec<WeaponID>, EquipError> {
+        let mut displaced = Vec::new();
+        match (&weapon.hand, slot) {
+            (_, EquipSlot::Armor | EquipSlot::Trinket) => return Err(EquipError::WrongSlot),
+            (WeaponHand::SPECIAL, EquipSlot::MainHand) => {
+                return Err(EquipError::SpecialOffHandOnly)
+            }
+            (WeaponHand::TWO, _) => {
+                displaced.extend(self.main_hand.take());
+                displaced.extend(self.off_hand.take());
+                self.main_hand = Some(weapon.id);
+                self.two_handed = true;
+            }
+            (_, slot) => {
+                if self.two_handed {
+                    displaced.extend(self.main_hand.take());
+                    self.two_handed = false;
+                }
+                let hand = match slot {
+                    EquipSlot::MainHand => &mut self.main_hand,
+                    _ => &mut self.off_hand,
+                };
+                displaced.extend(hand.replace(weapon.id));
+            }
+        }
+        Ok(displaced)
+    }
+
+    pub fn unequip(&mut self, slot: EquipSlot) -> Option<WeaponID> {
+        match slot {
+            EquipSlot::MainHand => {
+                self.two_handed = false;
+                self.main_hand.take()
+            }
+            EquipSlot::OffHand => self.off_hand.take(),
+            EquipSlot::Armor | EquipSlot::Trinket => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EquipSlot {
+    MainHand,
+    OffHand,
+    Armor,
+    Trinket,
+}
+impl Display for EquipSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MainHand => write!(f, "Main Hand"),
+            Self::OffHand => write!(f, "Off Hand"),
+            Self::Armor => write!(f, "Armor"),
+            Self::Trinket => write!(f, "Trinket"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EquipError {
+    WrongSlot,
+    SpecialOffHandOnly,
+}
+impl Display for EquipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongSlot => write!(f, "Weapons Only Fit Hand Slots"),
+            Self::SpecialOffHandOnly => write!(f, "Special Weapons Only Fit The Off Hand"),
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct EquipWeapon {
+    pub entity: Entity,
+    pub weapon: WeaponID,
+    pub slot: EquipSlot,
+}
+
+#[derive(Event)]
+pub struct UnequipWeapon {
+    pub entity: Entity,
+    pub slot: EquipSlot,
+}
+
+// EVERY NEW CHARACTER STARTS HOLDING ITS DEFAULT WEAPON
+pub fn equip_default_weapon(
+    mut commands: Commands,
+    query_character: Query<(Entity, &WeaponID), (Added<Character>, Without<Equipment>)>,
+    weapons: Res<Weapons>,
+) {
+    for (entity, dwid) in query_character.iter() {
+        let mut equipment = Equipment::default();
+        if let Some(weapon) = weapons.get(*dwid) {
+            if let Some(slot) = equipment.free_slot(weapon) {
+                let _ = equipment.equip(weapon, slot);
+            }
+        }
+        commands.entity(entity).insert(equipment);
+    }
+}
+
+// LOOTED WEAPONS ARE HELD IF A HAND IS FREE, OTHERWISE STORED
+pub fn evr_equip_item(
+    mut evr_equip_item: EventReader<EquipItem>,
+    mut evw_equip_weapon: EventWriter<EquipWeapon>,
+    mut evw_store_item: EventWriter<StoreItem>,
+    query_player: Query<(Entity, &Equipment), With<Player>>,
+    weapons: Res<Weapons>,
+) {
+    for ev in evr_equip_item.read() {
+        let slot = weapons.get(ev.weapon).and_then(|weapon| {
+            query_player
+                .iter()
+                .find_map(|(entity, equipment)| Some((entity, equipment.free_slot(weapon)?)))
+        });
+        match slot {
+            Some((entity, slot)) => {
+                evw_equip_weapon.send(EquipWeapon {
+                    entity,
+                    weapon: ev.weapon,
+                    slot,
+                });
+            }
+            None => {
+                evw_store_item.send(StoreItem(ItemStack {
+                    item: ev.item,
+                    quantity: 1,
+                }));
+            }
+        }
+    }
+}
+
+pub fn evr_equip_weapon(
+    mut evr_equip_weapon: EventReader<EquipWeapon>,
+    mut evw_store_item: EventWriter<StoreItem>,
+    mut query_equipment: Query<&mut Equipment>,
+    weapons: Res<Weapons>,
+    items: Res<Items>,
+) {
+    for ev in evr_equip_weapon.read() {
+        let Ok(mut equipment) = query_equipment.get_mut(ev.entity) else {
+            continue;
+        };
+        let Some(weapon) = weapons.get(ev.weapon) else {
+            warn!("[SKIPPED] EquipWeapon {} (unknown)", *ev.weapon.0);
+            continue;
+        };
+        match equipment.equip(weapon, ev.slot) {
+            Ok(displaced) => {
+                info!("[EQUIPPED] {} >> {}", *weapon.title, ev.slot);
+                for wid in displaced {
+                    store_weapon(wid, &items, &mut evw_store_item);
+                }
+            }
+            Err(e) => warn!("[ERROR] EquipWeapon {}: {}", *weapon.title, e),
+        }
+    }
+}
+
+pub fn evr_unequip_weapon(
+    mut evr_unequip_weapon: EventReader<UnequipWeapon>,
+    mut evw_store_item: EventWriter<StoreItem>,
+    mut query_equipment: Query<&mut Equipment>,
+    items: Res<Items>,
+) {
+    for ev in evr_unequip_weapon.read() {
+        let Ok(mut equipment) = query_equipment.get_mut(ev.entity) else {
+            continue;
+        };
+        if let Some(wid) = equipment.unequip(ev.slot) {
+            info!("[UNEQUIPPED] {}", ev.slot);
+            store_weapon(wid, &items, &mut evw_store_item);
+        }
+    }
+}
+
+// TAKEN OFF WEAPONS GO BACK TO THE INVENTORY AS THEIR ITEM
+fn store_weapon(wid: WeaponID, items: &Items, evw_store_item: &mut EventWriter<StoreItem>) {
+    match items.for_weapon(wid) {
+        Some(item) => {
+            evw_store_item.send(StoreItem(ItemStack {
+                item: item.id,
+                quantity: 1,
+            }));
+        }
+        None => warn!("[DISCARDED] Weapon {} (no matching item)", *wid.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ID;
+
+    fn weapon(id: usize, hand: WeaponHand) -> Weapon {
+        Weapon {
+            id: WeaponID(ID(id)),
+            hand,
+            ..Weapon::default()
+        }
+    }
+
+    #[test]
+    fn free_slot_fills_main_hand_then_off_hand() {
+        let mut equipment = Equipment::default();
+        let one = weapon(1, WeaponHand::ONE);
+        assert_eq!(equipment.free_slot(&one), Some(EquipSlot::MainHand));
+        equipment.equip(&one, EquipSlot::MainHand).unwrap();
+        assert_eq!(equipment.free_slot(&one), Some(EquipSlot::OffHand));
+        equipment.equip(&one, EquipSlot::OffHand).unwrap();
+        assert_eq!(equipment.free_slot(&one), None);
+    }
+
+    #[test]
+    fn two_handed_occupies_both_hands() {
+        let mut equipment = Equipment::default();
+        let one = weapon(1, WeaponHand::ONE);
+        let two = weapon(2, WeaponHand::TWO);
+        equipment.equip(&one, EquipSlot::MainHand).unwrap();
+        equipment.equip(&one, EquipSlot::OffHand).unwrap();
+        let displaced = equipment.equip(&two, EquipSlot::MainHand).unwrap();
+        assert_eq!(displaced.len(), 2);
+        assert!(equipment.two_handed);
+        assert_eq!(equipment.off_hand.map(|wid| *wid.0), None);
+        assert_eq!(equipment.attack_weapon().map(|wid| *wid.0), Some(2));
+        // NOTHING ELSE FITS WHILE BOTH HANDS ARE TAKEN
+        assert_eq!(equipment.free_slot(&one), None);
+        assert_eq!(equipment.free_slot(&weapon(3, WeaponHand::SPECIAL)), None);
+        assert_eq!(equipment.free_slot(&two), None);
+    }
+
+    #[test]
+    fn one_handed_replaces_a_two_handed_weapon() {
+        let mut equipment = Equipment::default();
+        equipment
+            .equip(&weapon(2, WeaponHand::TWO), EquipSlot::MainHand)
+            .unwrap();
+        let displaced = equipment
+            .equip(&weapon(1, WeaponHand::ONE), EquipSlot::OffHand)
+            .unwrap();
+        assert_eq!(
+            displaced.iter().map(|wid| *wid.0).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert!(!equipment.two_handed);
+        assert_eq!(equipment.main_hand.map(|wid| *wid.0), None);
+        assert_eq!(equipment.off_hand.map(|wid| *wid.0), Some(1));
+    }
+
+    #[test]
+    fn special_is_off_hand_only() {
+        let mut equipment = Equipment::default();
+        let special = weapon(3, WeaponHand::SPECIAL);
+        assert_eq!(equipment.free_slot(&special), Some(EquipSlot::OffHand));
+        assert_eq!(
+            equipment.equip(&special, EquipSlot::MainHand).err(),
+            Some(EquipError::SpecialOffHandOnly)
+        );
+        assert!(equipment.main_hand.is_none());
+        equipment.equip(&special, EquipSlot::OffHand).unwrap();
+        assert_eq!(equipment.free_slot(&special), None);
+        // THE MAIN HAND IS STILL FREE FOR A ONE HANDED WEAPON
+        assert_eq!(
+            equipment.free_slot(&weapon(1, WeaponHand::ONE)),
+            Some(EquipSlot::MainHand)
+        );
+    }
+
+    #[test]
+    fn weapons_do_not_fit_armor_slots() {
+        let mut equipment = Equipment::default();
+        assert_eq!(
+            equipment
+                .equip(&weapon(1, WeaponHand::ONE), EquipSlot::Armor)
+                .err(),
+            Some(EquipError::WrongSlot)
+        );
+    }
+
+    #[test]
+    fn unequip_main_hand_frees_both_hands() {
+        let mut equipment = Equipment::default();
+        let two = weapon(2, WeaponHand::TWO);
+        equipment.equip(&two, EquipSlot::MainHand).unwrap();
+        assert_eq!(
+            equipment.unequip(EquipSlot::MainHand).map(|wid| *wid.0),
+            Some(2)
+        );
+        assert!(!equipment.two_handed);
+        assert_eq!(equipment.free_slot(&two), Some(EquipSlot::MainHand));
+    }
+
+    #[test]
+    fn slot_for_displaces_only_when_nothing_is_free() {
+        let mut equipment = Equipment::default();
+        let one = weapon(1, WeaponHand::ONE);
+        let special = weapon(3, WeaponHand::SPECIAL);
+        equipment.equip(&one, EquipSlot::MainHand).unwrap();
+        assert_eq!(equipment.slot_for(&one), EquipSlot::OffHand);
+        equipment.equip(&one, EquipSlot::OffHand).unwrap();
+        assert_eq!(equipment.slot_for(&one), EquipSlot::MainHand);
+        assert_eq!(equipment.slot_for(&special), EquipSlot::OffHand);
+        assert_eq!(
+            equipment.slot_for(&weapon(2, WeaponHand::TWO)),
+            EquipSlot::MainHand
+        );
+    }
+}
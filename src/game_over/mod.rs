@@ -0,0 +1,72 @@
+use crate::{
+    chance::GameRng,
+    player::{PlayerDistance, PlayerKills, PlayerLoot},
+    theme::{FontRole, Theme},
+    ui::{MenuAction, MenuAppExt, MenuBuilder, Screens},
+    AppState,
+};
+use bevy::prelude::*;
+
+pub struct GameOverPlugin;
+impl Plugin for GameOverPlugin {
+    fn name(&self) -> &str {
+        "Game Over Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_menu_action::<GameOverButton>()
+            .add_systems(OnEnter(AppState::GameOver), spawn_game_over)
+            .add_systems(Update, return_to_menu.run_if(in_state(AppState::GameOver)))
+            .add_systems(OnExit(AppState::GameOver), despawn_game_over);
+    }
+}
+
+// attached to all game over components for cleanup
+#[derive(Component, Clone, Copy)]
+pub struct CleanupGameOver;
+
+#[derive(Component, Clone, Copy)]
+pub struct GameOverButton;
+
+fn spawn_game_over(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    player_distance: Res<PlayerDistance>,
+    player_loot: Res<PlayerLoot>,
+    player_kills: Res<PlayerKills>,
+    rng: Res<GameRng>,
+) {
+    MenuBuilder::new()
+        .title("GAME OVER")
+        .text(
+            format!("Distance: {:.0}", **player_distance),
+            FontRole::Body,
+        )
+        .text(format!("Loot: {}", **player_loot), FontRole::Body)
+        .text(format!("Kills: {}", **player_kills), FontRole::Body)
+        .text(format!("Seed: {}", rng.seed()), FontRole::Small)
+        .row([("MENU", GameOverButton)])
+        .spawn(&mut commands, &theme, CleanupGameOver);
+    info!("[SPAWNED] Game Over Entities");
+}
+
+fn despawn_game_over(
+    mut commands: Commands,
+    query_game_over: Query<Entity, With<CleanupGameOver>>,
+) {
+    for entity in query_game_over.iter() {
+        commands.entity(entity).despawn_recursive();
+        info!("[DESPAWNED] Game Over Entities.");
+    }
+}
+
+fn return_to_menu(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut evr_menu_action: EventReader<MenuAction<GameOverButton>>,
+    mut screens: Screens,
+) {
+    let pressed = evr_menu_action.read().count() > 0;
+    if pressed || keys.any_just_pressed([KeyCode::Enter, KeyCode::Escape]) {
+        screens.goto(AppState::Menu);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    character::Constitution,
+    inventory::{Inventory, RemoveItem},
+    item::{ItemKind, ItemStack, Items},
+    player::{Lane, Player},
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub struct HealthPlugin;
+impl Plugin for HealthPlugin {
+    fn name(&self) -> &str {
+        "Health Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_event::<TakeDamage>()
+            .add_event::<Heal>()
+            .add_event::<Died>()
+            .add_event::<PatchUp>()
+            .add_systems(
+                Update,
+                (evr_take_damage, evr_patch_up, evr_heal)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+pub const HEALTH_PER_CONSTITUTION: i32 = 3;
+pub const HEAL_PER_CONSUMABLE: i32 = 2;
+
+#[derive(Component, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct Health {
+    pub current: i32,
+    pub max: i32,
+}
+impl Health {
+    pub fn from_constitution(constitution: &Constitution) -> Self {
+        let max = (constitution.0 .0 * HEALTH_PER_CONSTITUTION).max(1);
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0
+    }
+}
+
+#[derive(Event)]
+pub struct TakeDamage {
+    pub target: Entity,
+    pub amount: i32,
+}
+
+#[derive(Event)]
+pub struct Heal {
+    pub target: Entity,
+    pub amount: i32,
+}
+
+#[derive(Event, Deref)]
+pub struct Died(pub Entity);
+
+// sent when the player chooses to spend consumables on the wounded
+#[derive(Event)]
+pub struct PatchUp;
+
+pub fn evr_take_damage(
+    mut evr_take_damage: EventReader<TakeDamage>,
+    mut evw_died: EventWriter<Died>,
+    mut query_health: Query<&mut Health>,
+) {
+    for ev in evr_take_damage.read() {
+        let Ok(mut health) = query_health.get_mut(ev.target) else {
+            continue;
+        };
+        if health.is_dead() {
+            continue;
+        }
+        health.current = (health.current - ev.amount.max(0)).max(0);
+        info!(
+            "[EVENT] [READ] TakeDamage({}) >> {}/{}",
+            ev.amount, health.current, health.max
+        );
+        if health.is_dead() {
+            evw_died.send(Died(ev.target));
+            info!("[EVENT] [WRITE] Died");
+        }
+    }
+}
+
+pub fn evr_heal(mut evr_heal: EventReader<Heal>, mut query_health: Query<&mut Health>) {
+    for ev in evr_heal.read() {
+        let Ok(mut health) = query_health.get_mut(ev.target) else {
+            continue;
+        };
+        if health.is_dead() {
+            continue;
+        }
+        health.current = (health.current + ev.amount.max(0)).min(health.max);
+        info!(
+            "[EVENT] [READ] Heal({}) >> {}/{}",
+            ev.amount, health.current, health.max
+        );
+    }
+}
+
+// CONSUMABLE STACKS IN THE INVENTORY, ONE ENTRY PER ITEM
+pub fn consumables(inventory: &Inventory, items: &Items) -> Vec<ItemStack> {
+    let mut supplies: Vec<ItemStack> = Vec::new();
+    for stack in inventory.stacks.iter() {
+        if !matches!(
+            items.get(stack.item).map(|item| item.kind),
+            Some(ItemKind::CONSUMABLE)
+        ) {
+            continue;
+        }
+        match supplies
+            .iter_mut()
+            .find(|supply| *supply.item.0 == *stack.item.0)
+        {
+            Some(supply) => supply.quantity += stack.quantity,
+            None => supplies.push(*stack),
+        }
+    }
+    supplies
+}
+
+// HOW MANY OF EACH SUPPLY EVERY WOUNDED MEMBER USES, IN THE ORDER GIVEN
+// EACH ONE HEALS HEAL_PER_CONSUMABLE, THE LAST MAY OVERHEAL
+pub fn plan_patch_up(
+    wounded: &[(Entity, i32)],
+    supplies: &[ItemStack],
+) -> Vec<(Entity, ItemStack)> {
+    let mut supplies = supplies.to_vec();
+    let mut plan = Vec::new();
+    for (entity, missing) in wounded.iter() {
+        let mut missing = *missing;
+        for supply in supplies.iter_mut() {
+            if missing <= 0 {
+                break;
+            }
+            let used = (missing as u32)
+                .div_ceil(HEAL_PER_CONSUMABLE as u32)
+                .min(supply.quantity);
+            if used == 0 {
+                continue;
+            }
+            supply.quantity -= used;
+            missing -= used as i32 * HEAL_PER_CONSUMABLE;
+            plan.push((
+                *entity,
+                ItemStack {
+                    item: supply.item,
+                    quantity: used,
+                },
+            ));
+        }
+    }
+    plan
+}
+
+// LIVING PARTY MEMBERS BELOW FULL HEALTH AND WHAT THEY ARE MISSING, FRONT LANE FIRST
+pub fn wounded<'a>(
+    party: impl Iterator<Item = (Entity, &'a Lane, &'a Health)>,
+) -> Vec<(Entity, i32)> {
+    let mut wounded = party
+        .filter(|(_, _, health)| !health.is_dead() && health.current < health.max)
+        .collect::<Vec<_>>();
+    wounded.sort_by_key(|(_, lane, _)| **lane);
+    wounded
+        .into_iter()
+        .map(|(entity, _, health)| (entity, health.max - health.current))
+        .collect()
+}
+
+pub fn evr_patch_up(
+    mut evr_patch_up: EventReader<PatchUp>,
+    query_party: Query<(Entity, &Lane, &Health), With<Player>>,
+    inventory: Res<Inventory>,
+    items: Res<Items>,
+    mut evw_heal: EventWriter<Heal>,
+    mut evw_remove_item: EventWriter<RemoveItem>,
+) {
+    // ONE PASS COVERS EVERYONE, REPEATS WOULD PLAN AGAINST THE SAME INVENTORY
+    if evr_patch_up.read().count() == 0 {
+        return;
+    }
+    let plan = plan_patch_up(
+        &wounded(query_party.iter()),
+        &consumables(&inventory, &items),
+    );
+    for (entity, stack) in plan {
+        evw_remove_item.send(RemoveItem(stack));
+        evw_heal.send(Heal {
+            target: entity,
+            amount: stack.quantity as i32 * HEAL_PER_CONSUMABLE,
+        });
+        info!(
+            "[EVENT] [WRITE] Heal (Item {} x{})",
+            *stack.item.0, stack.quantity
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{item::ItemID, ID};
+
+    fn supply(item: usize, quantity: u32) -> ItemStack {
+        ItemStack {
+            item: ItemID(ID(item)),
+            quantity,
+        }
+    }
+
+    fn spent(plan: &[(Entity, ItemStack)], entity: Entity) -> Vec<(usize, u32)> {
+        plan.iter()
+            .filter(|(target, _)| *target == entity)
+            .map(|(_, stack)| (*stack.item.0, stack.quantity))
+            .collect()
+    }
+
+    #[test]
+    fn spends_enough_to_cover_what_is_missing() {
+        let member = Entity::from_raw(1);
+        // 3 MISSING AT 2 PER CONSUMABLE ROUNDS UP TO 2
+        let plan = plan_patch_up(&[(member, 3)], &[supply(0, 5)]);
+        assert_eq!(spent(&plan, member), vec![(0, 2)]);
+        let plan = plan_patch_up(&[(member, 4)], &[supply(0, 5)]);
+        assert_eq!(spent(&plan, member), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn front_lane_is_served_first() {
+        let front = Entity::from_raw(1);
+        let back = Entity::from_raw(2);
+        let plan = plan_patch_up(&[(front, 4), (back, 4)], &[supply(0, 3)]);
+        assert_eq!(spent(&plan, front), vec![(0, 2)]);
+        assert_eq!(spent(&plan, back), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn spills_over_into_the_next_supply() {
+        let member = Entity::from_raw(1);
+        let plan = plan_patch_up(&[(member, 6)], &[supply(0, 1), supply(1, 5)]);
+        assert_eq!(spent(&plan, member), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn nothing_spent_without_wounded_or_supplies() {
+        let member = Entity::from_raw(1);
+        assert!(plan_patch_up(&[], &[supply(0, 5)]).is_empty());
+        assert!(plan_patch_up(&[(member, 3)], &[]).is_empty());
+        assert!(plan_patch_up(&[(member, 3)], &[supply(0, 0)]).is_empty());
+    }
+}
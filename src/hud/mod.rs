@@ -0,0 +1,356 @@
+use crate::{
+    area::CurrentArea,
+    combat::{resolve_combat, Opponent},
+    health::{Died, Health},
+    item::{CollectItem, Items},
+    loading::{IconAssets, ItemAssets},
+    player::{Player, PlayerDistance, PlayerLoot},
+    theme::{ColorRole, FontRole, Theme},
+    AppState, RunSet, Title,
+};
+use bevy::prelude::*;
+
+pub struct HudPlugin;
+impl Plugin for HudPlugin {
+    fn name(&self) -> &str {
+        "Hud Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_event::<HudMessage>()
+            .add_systems(OnEnter(AppState::Playing), spawn_hud.after(RunSet::Restore))
+            .add_systems(
+                Update,
+                (
+                    update_money.run_if(resource_changed::<PlayerLoot>),
+                    update_distance.run_if(resource_changed::<PlayerDistance>),
+                    update_area_title.run_if(resource_changed::<CurrentArea>),
+                    update_party,
+                    // THE DEFEATED OPPONENT IS STILL AROUND TO READ ITS TITLE
+                    feed_game_events.before(resolve_combat),
+                    (evr_hud_message, expire_messages).chain(),
+                )
+                    .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(OnExit(AppState::Playing), despawn_hud);
+    }
+}
+
+// NOTE: SIZES ARE IN PERCENT OF THE WINDOW HEIGHT SO THE HUD SCALES WITH IT
+pub const HUD_MARGIN: f32 = 2.;
+pub const HUD_ICON_SIZE: f32 = 5.;
+pub const PORTRAIT_SIZE: f32 = 9.;
+pub const HEART_SIZE: f32 = 3.;
+// SECONDS A FEED MESSAGE STAYS UP, THE OLDEST GO FIRST PAST THE LIMIT
+pub const MESSAGE_DURATION: f32 = 5.;
+pub const MESSAGE_LIMIT: usize = 5;
+
+// attached to the hud root for cleanup
+#[derive(Component, Clone, Copy)]
+pub struct CleanupHud;
+
+#[derive(Component, Clone, Copy)]
+pub struct HudMoney;
+
+#[derive(Component, Clone, Copy)]
+pub struct HudDistance;
+
+#[derive(Component, Clone, Copy)]
+pub struct HudAreaTitle;
+
+// column of party members, top left
+#[derive(Component, Clone, Copy)]
+pub struct HudParty;
+
+// one portrait and row of hearts per party member
+#[derive(Component, Clone, Copy, Deref)]
+pub struct HudPartyMember(pub Entity);
+
+// column of recent messages, bottom centre
+#[derive(Component, Clone, Copy)]
+pub struct HudFeed;
+
+#[derive(Component, Deref, DerefMut)]
+pub struct HudFeedMessage(pub Timer);
+
+// a line for the message feed, e.g. loot picked up or an enemy defeated
+#[derive(Event)]
+pub struct HudMessage(pub String);
+
+fn spawn_hud(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    item_assets: Res<ItemAssets>,
+    player_loot: Res<PlayerLoot>,
+    player_distance: Res<PlayerDistance>,
+    current_area: Res<CurrentArea>,
+) {
+    let margin = Val::Vh(HUD_MARGIN);
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            CleanupHud,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: margin,
+                    left: margin,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Vh(1.),
+                    ..default()
+                },
+                HudParty,
+            ));
+            parent
+                .spawn(Node {
+                    position_type: PositionType::Absolute,
+                    top: margin,
+                    width: Val::Percent(100.),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                })
+                .with_child((
+                    Text::new(current_area.title.to_uppercase()),
+                    theme.text(FontRole::Heading),
+                    HudAreaTitle,
+                ));
+            parent
+                .spawn(Node {
+                    position_type: PositionType::Absolute,
+                    bottom: margin,
+                    left: margin,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Vh(1.),
+                    ..default()
+                })
+                .with_children(|wallet| {
+                    wallet
+                        .spawn(Node {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Vh(1.),
+                            ..default()
+                        })
+                        .with_children(|money| {
+                            money.spawn((
+                                ImageNode::new(item_assets.item_money_0.clone()),
+                                Node {
+                                    width: Val::Vh(HUD_ICON_SIZE),
+                                    height: Val::Vh(HUD_ICON_SIZE),
+                                    ..default()
+                                },
+                            ));
+                            money.spawn((
+                                Text::new(format!("{}", **player_loot)),
+                                theme.text(FontRole::Body),
+                                HudMoney,
+                            ));
+                        });
+                    wallet.spawn((
+                        Text::new(distance_label(**player_distance)),
+                        theme.text(FontRole::Small),
+                        HudDistance,
+                    ));
+                });
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: margin,
+                    width: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                HudFeed,
+            ));
+        });
+    info!("[SPAWNED] Hud Entities");
+}
+
+fn despawn_hud(mut commands: Commands, query_hud: Query<Entity, With<CleanupHud>>) {
+    for entity in query_hud.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("[DESPAWNED] Hud Entities.");
+}
+
+fn distance_label(distance: f32) -> String {
+    format!("DISTANCE {:.0}", distance)
+}
+
+fn update_money(player_loot: Res<PlayerLoot>, mut query_text: Query<&mut Text, With<HudMoney>>) {
+    for mut text in query_text.iter_mut() {
+        **text = format!("{}", **player_loot);
+    }
+}
+
+// DISTANCE TICKS EVERY FRAME, ONLY A NEW WHOLE NUMBER TOUCHES THE TEXT
+fn update_distance(
+    player_distance: Res<PlayerDistance>,
+    mut query_text: Query<&mut Text, With<HudDistance>>,
+) {
+    let label = distance_label(**player_distance);
+    for mut text in query_text.iter_mut() {
+        if **text != label {
+            **text = label.clone();
+        }
+    }
+}
+
+fn update_area_title(
+    current_area: Res<CurrentArea>,
+    mut query_text: Query<&mut Text, With<HudAreaTitle>>,
+    mut evw_hud_message: EventWriter<HudMessage>,
+) {
+    let title = current_area.title.to_uppercase();
+    for mut text in query_text.iter_mut() {
+        **text = title.clone();
+    }
+    evw_hud_message.send(HudMessage(format!("ENTERED {}", title)));
+}
+
+// REBUILDS A PARTY MEMBER'S PORTRAIT AND HEARTS WHENEVER THEIR HEALTH CHANGES
+fn update_party(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    icon_assets: Res<IconAssets>,
+    query_player: Query<(Entity, &Title, &Health, &Sprite), (With<Player>, Changed<Health>)>,
+    query_member: Query<(Entity, &HudPartyMember)>,
+    query_party: Query<Entity, With<HudParty>>,
+) {
+    let Ok(party) = query_party.get_single() else {
+        return;
+    };
+    for (owner, title, health, sprite) in query_player.iter() {
+        let member = match query_member.iter().find(|(_, member)| ***member == owner) {
+            Some((member, _)) => {
+                commands.entity(member).despawn_descendants();
+                member
+            }
+            None => {
+                let member = commands
+                    .spawn((
+                        Node {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Vh(1.),
+                            ..default()
+                        },
+                        HudPartyMember(owner),
+                    ))
+                    .id();
+                commands.entity(party).add_child(member);
+                member
+            }
+        };
+        commands.entity(member).with_children(|parent| {
+            parent.spawn((
+                ImageNode::new(sprite.image.clone()),
+                Node {
+                    width: Val::Vh(PORTRAIT_SIZE),
+                    height: Val::Vh(PORTRAIT_SIZE),
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Vh(0.5),
+                    ..default()
+                })
+                .with_children(|status| {
+                    status.spawn((Text::new(title.to_uppercase()), theme.text(FontRole::Small)));
+                    status
+                        .spawn(Node {
+                            column_gap: Val::Vh(0.5),
+                            ..default()
+                        })
+                        .with_children(|hearts| {
+                            for i in 0..health.max {
+                                let node = Node {
+                                    width: Val::Vh(HEART_SIZE),
+                                    height: Val::Vh(HEART_SIZE),
+                                    ..default()
+                                };
+                                let heart = icon_assets.icon_heart_0.clone();
+                                // EMPTY HEARTS ARE TINTED BY THE THEME, FULL ONES KEEP THE SPRITE'S OWN
+                                if i >= health.current {
+                                    hearts.spawn((theme.image(heart, ColorRole::EmptyHeart), node));
+                                } else {
+                                    hearts.spawn((ImageNode::new(heart), node));
+                                }
+                            }
+                        });
+                });
+        });
+    }
+}
+
+// TURNS WHAT HAPPENED THIS FRAME INTO FEED MESSAGES
+fn feed_game_events(
+    mut evr_collect_item: EventReader<CollectItem>,
+    mut evr_died: EventReader<Died>,
+    query_opponent: Query<&Title, With<Opponent>>,
+    items: Res<Items>,
+    mut evw_hud_message: EventWriter<HudMessage>,
+) {
+    for ev in evr_collect_item.read() {
+        if let Some(item) = items.get(ev.0.item) {
+            evw_hud_message.send(HudMessage(format!(
+                "+{} {}",
+                ev.0.quantity,
+                item.title.to_uppercase()
+            )));
+        }
+    }
+    for ev in evr_died.read() {
+        if let Ok(title) = query_opponent.get(**ev) {
+            evw_hud_message.send(HudMessage(format!("{} DEFEATED", title.to_uppercase())));
+        }
+    }
+}
+
+fn evr_hud_message(
+    mut commands: Commands,
+    mut evr_hud_message: EventReader<HudMessage>,
+    theme: Res<Theme>,
+    query_feed: Query<(Entity, Option<&Children>), With<HudFeed>>,
+) {
+    let Ok((feed, children)) = query_feed.get_single() else {
+        return;
+    };
+    let mut count = children.map_or(0, |children| children.len());
+    for ev in evr_hud_message.read() {
+        commands.entity(feed).with_child((
+            Text::new(ev.0.clone()),
+            theme.text(FontRole::Small),
+            HudFeedMessage(Timer::from_seconds(MESSAGE_DURATION, TimerMode::Once)),
+        ));
+        count += 1;
+        info!("[EVENT] [READ] HudMessage: {}", ev.0);
+    }
+    // NEWEST AT THE BOTTOM, SO THE FIRST CHILDREN ARE THE OLDEST
+    if let Some(children) = children {
+        for entity in children.iter().take(count.saturating_sub(MESSAGE_LIMIT)) {
+            commands.entity(*entity).despawn_recursive();
+        }
+    }
+}
+
+// GAME TIME SO THE FEED HOLDS WHILE PAUSED
+fn expire_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query_message: Query<(Entity, &mut HudFeedMessage)>,
+) {
+    for (entity, mut timer) in query_message.iter_mut() {
+        if timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
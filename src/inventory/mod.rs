@@ -0,0 +1,413 @@
+use crate::{
+    character::Strength,
+    hud::HudMessage,
+    item::{ItemID, ItemStack, Items, StoreItem},
+    player::Player,
+    AppState, RunSet,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub struct InventoryPlugin;
+impl Plugin for InventoryPlugin {
+    fn name(&self) -> &str {
+        "Inventory Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Inventory>()
+            .add_event::<RemoveItem>()
+            .add_event::<SplitStack>()
+            .add_event::<MergeStacks>()
+            .add_event::<InventoryChanged>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                reset_inventory.in_set(RunSet::Reset),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_capacity,
+                    // TAKEN OUT BEFORE ANYTHING IS PUT BACK, SO A SWAP NEVER OVERFLOWS
+                    evr_remove_item,
+                    evr_store_item,
+                    evr_split_stack,
+                    evr_merge_stacks,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+pub const BASE_CAPACITY: usize = 8;
+pub const SLOTS_PER_STRENGTH: usize = 2;
+
+// shared by the whole party, capacity is measured in stacks
+#[derive(Resource, Clone, Default, Deserialize, Serialize)]
+pub struct Inventory {
+    pub stacks: Vec<ItemStack>,
+    pub capacity: usize,
+}
+impl Inventory {
+    pub fn capacity_for_strength(strength: i32) -> usize {
+        BASE_CAPACITY + strength.max(0) as usize * SLOTS_PER_STRENGTH
+    }
+
+    pub fn count(&self, item: ItemID) -> u32 {
+        self.stacks
+            .iter()
+            .filter(|stack| *stack.item.0 == *item.0)
+            .map(|stack| stack.quantity)
+            .sum()
+    }
+
+    // TOPS UP EXISTING STACKS FIRST, RETURNS WHATEVER DID NOT FIT
+    pub fn add(&mut self, stack: ItemStack, max_stack: u32) -> u32 {
+        let max_stack = max_stack.max(1);
+        let mut remaining = stack.quantity;
+        for existing in self
+            .stacks
+            .iter_mut()
+            .filter(|existing| *existing.item.0 == *stack.item.0)
+        {
+            let moved = remaining.min(max_stack.saturating_sub(existing.quantity));
+            existing.quantity += moved;
+            remaining -= moved;
+        }
+        while remaining > 0 && self.stacks.len() < self.capacity {
+            let moved = remaining.min(max_stack);
+            self.stacks.push(ItemStack {
+                item: stack.item,
+                quantity: moved,
+            });
+            remaining -= moved;
+        }
+        remaining
+    }
+
+    // ALL OR NOTHING, TAKES FROM THE LAST STACKS FIRST
+    pub fn remove(&mut self, stack: ItemStack) -> std::result::Result<(), InventoryError> {
+        if self.count(stack.item) < stack.quantity {
+            return Err(InventoryError::NotEnough);
+        }
+        let mut remaining = stack.quantity;
+        for existing in self
+            .stacks
+            .iter_mut()
+            .rev()
+            .filter(|existing| *existing.item.0 == *stack.item.0)
+        {
+            let moved = remaining.min(existing.quantity);
+            existing.quantity -= moved;
+            remaining -= moved;
+        }
+        self.stacks.retain(|existing| existing.quantity > 0);
+        Ok(())
+    }
+
+    // WHETHER EVERYTHING `returned` FITS ONCE `taken` IS OUT, E.G. WEAPONS DISPLACED BY AN EQUIP
+    pub fn fits_after_taking(&self, taken: ItemStack, returned: &[(ItemStack, u32)]) -> bool {
+        let mut after = self.clone();
+        if after.remove(taken).is_err() {
+            return false;
+        }
+        returned
+            .iter()
+            .all(|(stack, max_stack)| after.add(*stack, *max_stack) == 0)
+    }
+
+    // MOVES `quantity` FROM `slot` INTO A NEW STACK, RETURNS THE NEW SLOT
+    pub fn split(
+        &mut self,
+        slot: usize,
+        quantity: u32,
+    ) -> std::result::Result<usize, InventoryError> {
+        let stack = self.stacks.get(slot).ok_or(InventoryError::InvalidSlot)?;
+        if quantity == 0 || quantity >= stack.quantity {
+            return Err(InventoryError::InvalidQuantity);
+        }
+        if self.stacks.len() >= self.capacity {
+            return Err(InventoryError::Full);
+        }
+        let item = stack.item;
+        self.stacks[slot].quantity -= quantity;
+        self.stacks.push(ItemStack { item, quantity });
+        Ok(self.stacks.len() - 1)
+    }
+
+    // MOVES AS MUCH OF `from` INTO `into` AS FITS, DROPPING `from` IF EMPTIED
+    pub fn merge(
+        &mut self,
+        from: usize,
+        into: usize,
+        max_stack: u32,
+    ) -> std::result::Result<(), InventoryError> {
+        if from == into || from >= self.stacks.len() || into >= self.stacks.len() {
+            return Err(InventoryError::InvalidSlot);
+        }
+        if *self.stacks[from].item.0 != *self.stacks[into].item.0 {
+            return Err(InventoryError::ItemMismatch);
+        }
+        let moved = self.stacks[from]
+            .quantity
+            .min(max_stack.max(1).saturating_sub(self.stacks[into].quantity));
+        self.stacks[from].quantity -= moved;
+        self.stacks[into].quantity += moved;
+        if self.stacks[from].quantity == 0 {
+            self.stacks.remove(from);
+        }
+        Ok(())
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InventoryError {
+    Full,
+    NotEnough,
+    InvalidSlot,
+    InvalidQuantity,
+    ItemMismatch,
+}
+impl Display for InventoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full => write!(f, "Inventory Full"),
+            Self::NotEnough => write!(f, "Not Enough Items"),
+            Self::InvalidSlot => write!(f, "Invalid Slot"),
+            Self::InvalidQuantity => write!(f, "Invalid Quantity"),
+            Self::ItemMismatch => write!(f, "Item Mismatch"),
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct RemoveItem(pub ItemStack);
+
+#[derive(Event)]
+pub struct SplitStack {
+    pub slot: usize,
+    pub quantity: u32,
+}
+
+#[derive(Event)]
+pub struct MergeStacks {
+    pub from: usize,
+    pub into: usize,
+}
+
+// sent after any successful inventory operation
+#[derive(Event)]
+pub struct InventoryChanged;
+
+fn reset_inventory(mut inventory: ResMut<Inventory>) {
+    inventory.stacks.clear();
+}
+
+// CAPACITY SCALES WITH THE PARTY'S COMBINED STRENGTH
+pub fn update_capacity(
+    mut inventory: ResMut<Inventory>,
+    query_strength: Query<&Strength, With<Player>>,
+) {
+    let strength: i32 = query_strength.iter().map(|strength| strength.0 .0).sum();
+    let capacity = Inventory::capacity_for_strength(strength);
+    if inventory.capacity != capacity {
+        inventory.capacity = capacity;
+        info!("[MODIFIED] Inventory Capacity >> {}", capacity);
+    }
+}
+
+// WHATEVER DOESN'T FIT IS LEFT BEHIND, THE PLAYER IS TOLD WHAT WAS LOST
+pub fn evr_store_item(
+    mut evr_store_item: EventReader<StoreItem>,
+    mut evw_inventory_changed: EventWriter<InventoryChanged>,
+    mut evw_hud_message: EventWriter<HudMessage>,
+    mut inventory: ResMut<Inventory>,
+    items: Res<Items>,
+) {
+    for ev in evr_store_item.read() {
+        let item = items.get(ev.0.item);
+        let max_stack = item.map_or(1, |item| item.max_stack);
+        let leftover = inventory.add(ev.0, max_stack);
+        if leftover < ev.0.quantity {
+            evw_inventory_changed.send(InventoryChanged);
+        }
+        if leftover > 0 {
+            let title = item.map_or_else(
+                || format!("ITEM {}", *ev.0.item.0),
+                |item| item.title.to_uppercase(),
+            );
+            evw_hud_message.send(HudMessage(format!(
+                "INVENTORY FULL, {} x{} LEFT BEHIND",
+                title, leftover
+            )));
+            info!(
+                "[DISCARDED] Item {} x{} (Inventory Full)",
+                *ev.0.item.0, leftover
+            );
+        }
+    }
+}
+
+pub fn evr_remove_item(
+    mut evr_remove_item: EventReader<RemoveItem>,
+    mut evw_inventory_changed: EventWriter<InventoryChanged>,
+    mut inventory: ResMut<Inventory>,
+) {
+    for ev in evr_remove_item.read() {
+        match inventory.remove(ev.0) {
+            Ok(()) => {
+                evw_inventory_changed.send(InventoryChanged);
+            }
+            Err(e) => warn!("[ERROR] RemoveItem {}: {}", *ev.0.item.0, e),
+        }
+    }
+}
+
+pub fn evr_split_stack(
+    mut evr_split_stack: EventReader<SplitStack>,
+    mut evw_inventory_changed: EventWriter<InventoryChanged>,
+    mut inventory: ResMut<Inventory>,
+) {
+    for ev in evr_split_stack.read() {
+        match inventory.split(ev.slot, ev.quantity) {
+            Ok(_) => {
+                evw_inventory_changed.send(InventoryChanged);
+            }
+            Err(e) => warn!("[ERROR] SplitStack {}: {}", ev.slot, e),
+        }
+    }
+}
+
+pub fn evr_merge_stacks(
+    mut evr_merge_stacks: EventReader<MergeStacks>,
+    mut evw_inventory_changed: EventWriter<InventoryChanged>,
+    mut inventory: ResMut<Inventory>,
+    items: Res<Items>,
+) {
+    for ev in evr_merge_stacks.read() {
+        let max_stack = inventory
+            .stacks
+            .get(ev.into)
+            .and_then(|stack| items.get(stack.item))
+            .map_or(1, |item| item.max_stack);
+        match inventory.merge(ev.from, ev.into, max_stack) {
+            Ok(()) => {
+                evw_inventory_changed.send(InventoryChanged);
+            }
+            Err(e) => warn!("[ERROR] MergeStacks {} >> {}: {}", ev.from, ev.into, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ID;
+
+    const BANDAGE: ItemID = ItemID(ID(2));
+    const TWIG: ItemID = ItemID(ID(3));
+
+    fn stack(item: ItemID, quantity: u32) -> ItemStack {
+        ItemStack { item, quantity }
+    }
+
+    fn inventory(capacity: usize, stacks: &[ItemStack]) -> Inventory {
+        Inventory {
+            stacks: stacks.to_vec(),
+            capacity,
+        }
+    }
+
+    #[test]
+    fn capacity_scales_with_strength() {
+        assert_eq!(Inventory::capacity_for_strength(0), BASE_CAPACITY);
+        assert_eq!(
+            Inventory::capacity_for_strength(3),
+            BASE_CAPACITY + 3 * SLOTS_PER_STRENGTH
+        );
+        // NEGATIVE STRENGTH NEVER SHRINKS BELOW THE BASE
+        assert_eq!(Inventory::capacity_for_strength(-4), BASE_CAPACITY);
+    }
+
+    #[test]
+    fn add_tops_up_before_new_stacks() {
+        let mut inventory = inventory(4, &[stack(BANDAGE, 8)]);
+        assert_eq!(inventory.add(stack(BANDAGE, 15), 10), 0);
+        let quantities: Vec<u32> = inventory.stacks.iter().map(|s| s.quantity).collect();
+        assert_eq!(quantities, vec![10, 10, 3]);
+    }
+
+    #[test]
+    fn add_over_capacity_returns_leftover() {
+        let mut inventory = inventory(2, &[stack(TWIG, 50)]);
+        assert_eq!(inventory.add(stack(BANDAGE, 25), 10), 15);
+        assert_eq!(inventory.stacks.len(), 2);
+        assert_eq!(inventory.count(BANDAGE), 10);
+        assert_eq!(inventory.count(TWIG), 50);
+    }
+
+    #[test]
+    fn remove_is_all_or_nothing() {
+        let mut inventory = inventory(4, &[stack(BANDAGE, 10), stack(BANDAGE, 3)]);
+        assert_eq!(
+            inventory.remove(stack(BANDAGE, 14)),
+            Err(InventoryError::NotEnough)
+        );
+        assert_eq!(inventory.count(BANDAGE), 13);
+        assert_eq!(inventory.remove(stack(BANDAGE, 5)), Ok(()));
+        let quantities: Vec<u32> = inventory.stacks.iter().map(|s| s.quantity).collect();
+        assert_eq!(quantities, vec![8]);
+    }
+
+    #[test]
+    fn split_boundaries() {
+        let mut inventory = inventory(3, &[stack(BANDAGE, 4)]);
+        assert_eq!(inventory.split(1, 1), Err(InventoryError::InvalidSlot));
+        assert_eq!(inventory.split(0, 0), Err(InventoryError::InvalidQuantity));
+        assert_eq!(inventory.split(0, 4), Err(InventoryError::InvalidQuantity));
+        assert_eq!(inventory.split(0, 3), Ok(1));
+        assert_eq!(inventory.stacks[0].quantity, 1);
+        assert_eq!(inventory.stacks[1].quantity, 3);
+    }
+
+    #[test]
+    fn split_rejected_when_full() {
+        let mut inventory = inventory(2, &[stack(BANDAGE, 4), stack(TWIG, 1)]);
+        assert_eq!(inventory.split(0, 2), Err(InventoryError::Full));
+        assert_eq!(inventory.stacks[0].quantity, 4);
+    }
+
+    #[test]
+    fn merge_boundaries() {
+        let mut inventory = inventory(4, &[stack(BANDAGE, 6), stack(TWIG, 1), stack(BANDAGE, 7)]);
+        assert_eq!(inventory.merge(0, 0, 10), Err(InventoryError::InvalidSlot));
+        assert_eq!(inventory.merge(0, 3, 10), Err(InventoryError::InvalidSlot));
+        assert_eq!(inventory.merge(0, 1, 10), Err(InventoryError::ItemMismatch));
+        // ONLY WHAT FITS MOVES, THE REST STAYS BEHIND
+        assert_eq!(inventory.merge(2, 0, 10), Ok(()));
+        assert_eq!(inventory.stacks[0].quantity, 10);
+        assert_eq!(inventory.stacks[2].quantity, 3);
+    }
+
+    #[test]
+    fn merge_drops_the_emptied_stack() {
+        let mut inventory = inventory(4, &[stack(BANDAGE, 2), stack(BANDAGE, 3)]);
+        assert_eq!(inventory.merge(0, 1, 10), Ok(()));
+        assert_eq!(inventory.stacks.len(), 1);
+        assert_eq!(inventory.stacks[0].quantity, 5);
+    }
+
+    #[test]
+    fn swap_fits_into_the_slot_it_frees() {
+        let full = inventory(2, &[stack(BANDAGE, 1), stack(TWIG, 1)]);
+        let sword = (stack(ItemID(ID(4)), 1), 1);
+        assert!(full.fits_after_taking(stack(TWIG, 1), &[sword]));
+        // TWO DISPLACED WEAPONS NEED TWO SLOTS
+        assert!(!full.fits_after_taking(stack(TWIG, 1), &[sword, sword]));
+        // A PARTIAL STACK FREES NO SLOT
+        let partial = inventory(2, &[stack(BANDAGE, 1), stack(TWIG, 2)]);
+        assert!(!partial.fits_after_taking(stack(TWIG, 1), &[sword]));
+        assert!(!full.fits_after_taking(stack(ItemID(ID(9)), 1), &[]));
+    }
+}
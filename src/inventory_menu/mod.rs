@@ -0,0 +1,303 @@
+use crate::{
+    equipment::{EquipSlot, EquipWeapon, Equipment, UnequipWeapon},
+    health::{consumables, plan_patch_up, wounded, Health, PatchUp},
+    inventory::{Inventory, InventoryChanged, MergeStacks, RemoveItem, SplitStack},
+    item::{ItemKind, ItemStack, Items},
+    notification::Notify,
+    player::{Lane, Player},
+    theme::{ColorRole, FontRole, Theme},
+    ui::{MenuAction, MenuAppExt, MenuBuilder, Screens},
+    weapon::Weapons,
+    PauseState, Title,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+pub struct InventoryMenuPlugin;
+impl Plugin for InventoryMenuPlugin {
+    fn name(&self) -> &str {
+        "Inventory Menu Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_menu_action::<InventoryMenuButton>()
+            .add_systems(OnEnter(PauseState::Inventory), spawn_inventory_menu)
+            .add_systems(
+                Update,
+                (
+                    evr_inventory_menu,
+                    evr_equip_button,
+                    spawn_inventory_menu
+                        .run_if(on_event::<InventoryChanged>.or(party_equipment_changed)),
+                )
+                    .chain()
+                    .run_if(in_state(PauseState::Inventory)),
+            )
+            .add_systems(OnExit(PauseState::Inventory), despawn_inventory_menu);
+    }
+}
+
+// attached to all inventory menu components for cleanup
+#[derive(Component, Clone, Copy)]
+pub struct CleanupInventoryMenu;
+
+// slots index into Inventory::stacks as it was when the menu was built
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum InventoryMenuButton {
+    Split(usize),
+    Merge(usize, usize), // from, into
+    Drop(usize),
+    Equip(usize, Entity),
+    Unequip(Entity, EquipSlot),
+    PatchUp,
+    Back,
+}
+
+// the inventory edits the menu can make
+#[derive(SystemParam)]
+struct InventoryWriters<'w> {
+    remove_item: EventWriter<'w, RemoveItem>,
+    split_stack: EventWriter<'w, SplitStack>,
+    merge_stacks: EventWriter<'w, MergeStacks>,
+    equip_weapon: EventWriter<'w, EquipWeapon>,
+}
+
+fn party_equipment_changed(query_equipment: Query<(), (With<Player>, Changed<Equipment>)>) -> bool {
+    !query_equipment.is_empty()
+}
+
+// THE FIRST OTHER STACK OF THE SAME ITEM WITH ROOM LEFT
+fn merge_target(inventory: &Inventory, items: &Items, slot: usize) -> Option<usize> {
+    let stack = inventory.stacks.get(slot)?;
+    let max_stack = items.get(stack.item).map_or(1, |item| item.max_stack);
+    inventory
+        .stacks
+        .iter()
+        .enumerate()
+        .find(|(i, other)| {
+            *i != slot && *other.item.0 == *stack.item.0 && other.quantity < max_stack
+        })
+        .map(|(i, _)| i)
+}
+
+// REBUILT FROM SCRATCH AFTER EVERY CHANGE, SLOTS SHIFT WHEN A STACK EMPTIES
+fn spawn_inventory_menu(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    inventory: Res<Inventory>,
+    items: Res<Items>,
+    weapons: Res<Weapons>,
+    query_party: Query<(Entity, &Lane, &Title, &Equipment, &Health), With<Player>>,
+    query_inventory_menu: Query<Entity, With<CleanupInventoryMenu>>,
+) {
+    for entity in query_inventory_menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let mut menu = MenuBuilder::new().title("INVENTORY");
+    // HELD WEAPONS GO BACK INTO THE INVENTORY, SO THEY NEED A MATCHING ITEM AND A FREE SLOT
+    let has_room = inventory.stacks.len() < inventory.capacity;
+    let mut party = query_party.iter().collect::<Vec<_>>();
+    party.sort_by_key(|(_, lane, _, _, _)| **lane);
+    for (entity, _, title, equipment, _) in party.iter().copied() {
+        let hands = [
+            (EquipSlot::MainHand, equipment.main_hand),
+            (EquipSlot::OffHand, equipment.off_hand),
+        ];
+        for (slot, wid) in hands {
+            let Some(weapon) = wid.and_then(|wid| weapons.get(wid)) else {
+                continue;
+            };
+            let mut actions = vec![];
+            if has_room && items.for_weapon(weapon.id).is_some() {
+                actions.push(("UNEQUIP", InventoryMenuButton::Unequip(entity, slot)));
+            }
+            menu = menu.labelled_row(
+                format!(
+                    "{}: {} ({})",
+                    title.to_uppercase(),
+                    weapon.title.to_uppercase(),
+                    slot.to_string().to_uppercase()
+                ),
+                actions,
+            );
+        }
+    }
+    // ONLY OFFERED WHEN IT WOULD SPEND SOMETHING
+    let wounded = wounded(
+        party
+            .iter()
+            .map(|(entity, lane, _, _, health)| (*entity, *lane, *health)),
+    );
+    if !plan_patch_up(&wounded, &consumables(&inventory, &items)).is_empty() {
+        menu = menu.row([("PATCH UP", InventoryMenuButton::PatchUp)]);
+    }
+    menu = menu.text(
+        format!("{}/{} SLOTS", inventory.stacks.len(), inventory.capacity),
+        FontRole::Small,
+    );
+    if inventory.stacks.is_empty() {
+        menu = menu.text("EMPTY", FontRole::Body);
+    }
+    for (slot, stack) in inventory.stacks.iter().enumerate() {
+        let item = items.get(stack.item);
+        let title = item.map_or_else(
+            || format!("ITEM {}", *stack.item.0),
+            |item| item.title.to_uppercase(),
+        );
+        let mut actions = vec![];
+        // ANY PARTY MEMBER CAN TAKE A STORED WEAPON
+        if let Some(ItemKind::WEAPON(_)) = item.map(|item| item.kind) {
+            for (entity, _, member, _, _) in party.iter() {
+                actions.push((
+                    format!("EQUIP {}", member.to_uppercase()),
+                    InventoryMenuButton::Equip(slot, *entity),
+                ));
+            }
+        }
+        if stack.quantity > 1 && inventory.stacks.len() < inventory.capacity {
+            actions.push((String::from("SPLIT"), InventoryMenuButton::Split(slot)));
+        }
+        if let Some(into) = merge_target(&inventory, &items, slot) {
+            actions.push((
+                String::from("MERGE"),
+                InventoryMenuButton::Merge(slot, into),
+            ));
+        }
+        actions.push((String::from("DROP"), InventoryMenuButton::Drop(slot)));
+        menu = menu.labelled_row(format!("{} x{}", title, stack.quantity), actions);
+    }
+    let root = menu.row([("BACK", InventoryMenuButton::Back)]).spawn(
+        &mut commands,
+        &theme,
+        CleanupInventoryMenu,
+    );
+    commands
+        .entity(root)
+        .insert((theme.background(ColorRole::Overlay), GlobalZIndex(1)));
+    info!("[SPAWNED] Inventory Menu Entities");
+}
+
+fn despawn_inventory_menu(
+    mut commands: Commands,
+    query_inventory_menu: Query<Entity, With<CleanupInventoryMenu>>,
+) {
+    for entity in query_inventory_menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("[DESPAWNED] Inventory Menu Entities.");
+}
+
+fn evr_inventory_menu(
+    mut evr_menu_action: EventReader<MenuAction<InventoryMenuButton>>,
+    mut evw_inventory: InventoryWriters,
+    mut evw_unequip_weapon: EventWriter<UnequipWeapon>,
+    mut evw_patch_up: EventWriter<PatchUp>,
+    inventory: Res<Inventory>,
+    mut screens: Screens,
+) {
+    for ev in evr_menu_action.read() {
+        match **ev {
+            // HALF OF THE STACK, ROUNDED DOWN, MOVES TO A NEW SLOT
+            InventoryMenuButton::Split(slot) => {
+                let Some(stack) = inventory.stacks.get(slot) else {
+                    continue;
+                };
+                evw_inventory.split_stack.send(SplitStack {
+                    slot,
+                    quantity: stack.quantity / 2,
+                });
+                info!("[EVENT] [WRITE] SplitStack({})", slot);
+            }
+            InventoryMenuButton::Merge(from, into) => {
+                evw_inventory.merge_stacks.send(MergeStacks { from, into });
+                info!("[EVENT] [WRITE] MergeStacks({} >> {})", from, into);
+            }
+            InventoryMenuButton::Drop(slot) => {
+                let Some(stack) = inventory.stacks.get(slot) else {
+                    continue;
+                };
+                evw_inventory.remove_item.send(RemoveItem(*stack));
+                info!("[EVENT] [WRITE] RemoveItem({})", slot);
+            }
+            // SEE evr_equip_button
+            InventoryMenuButton::Equip(..) => {}
+            InventoryMenuButton::Unequip(entity, slot) => {
+                evw_unequip_weapon.send(UnequipWeapon { entity, slot });
+                info!("[EVENT] [WRITE] UnequipWeapon({})", slot);
+            }
+            InventoryMenuButton::PatchUp => {
+                evw_patch_up.send(PatchUp);
+                info!("[EVENT] [WRITE] PatchUp");
+            }
+            InventoryMenuButton::Back => screens.pop(),
+        }
+    }
+}
+
+// EQUIPS A STORED WEAPON, REFUSED WHEN WHAT IT DISPLACES HAS NOWHERE TO GO
+fn evr_equip_button(
+    mut evr_menu_action: EventReader<MenuAction<InventoryMenuButton>>,
+    mut evw_inventory: InventoryWriters,
+    mut evw_notify: EventWriter<Notify>,
+    inventory: Res<Inventory>,
+    items: Res<Items>,
+    weapons: Res<Weapons>,
+    query_equipment: Query<&Equipment>,
+) {
+    for ev in evr_menu_action.read() {
+        let InventoryMenuButton::Equip(slot, entity) = **ev else {
+            continue;
+        };
+        let Some(stack) = inventory.stacks.get(slot) else {
+            continue;
+        };
+        let weapon = items.get(stack.item).and_then(|item| match item.kind {
+            ItemKind::WEAPON(wid) => weapons.get(wid),
+            _ => None,
+        });
+        let (Some(weapon), Ok(equipment)) = (weapon, query_equipment.get(entity)) else {
+            continue;
+        };
+        // TRIED ON A COPY FIRST TO SEE WHAT COMES OFF
+        let equip_slot = equipment.slot_for(weapon);
+        let mut preview = *equipment;
+        let displaced = match preview.equip(weapon, equip_slot) {
+            Ok(displaced) => displaced,
+            Err(e) => {
+                evw_notify.send(Notify::error(e.to_string().to_uppercase()));
+                continue;
+            }
+        };
+        let taken = ItemStack {
+            item: stack.item,
+            quantity: 1,
+        };
+        let returned = displaced
+            .iter()
+            .filter_map(|wid| items.for_weapon(*wid))
+            .map(|item| {
+                let stack = ItemStack {
+                    item: item.id,
+                    quantity: 1,
+                };
+                (stack, item.max_stack)
+            })
+            .collect::<Vec<_>>();
+        if !inventory.fits_after_taking(taken, &returned) {
+            let titles = returned
+                .iter()
+                .filter_map(|(stack, _)| items.get(stack.item))
+                .map(|item| item.title.to_uppercase())
+                .collect::<Vec<_>>();
+            evw_notify.send(Notify::error(format!("NO ROOM FOR {}", titles.join(", "))));
+            info!("[SKIPPED] EquipWeapon {} (Inventory Full)", *weapon.title);
+            continue;
+        }
+        evw_inventory.equip_weapon.send(EquipWeapon {
+            entity,
+            weapon: weapon.id,
+            slot: equip_slot,
+        });
+        evw_inventory.remove_item.send(RemoveItem(taken));
+        info!("[EVENT] [WRITE] EquipWeapon({}) >> {}", slot, equip_slot);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    chance::{AliasTable, ChanceKind, GameRng},
+    loading::RonAssetPlugin,
+    player::PlayerLoot,
+    weapon::WeaponID,
+    AppState, Title, ID,
+};
+use bevy::{prelude::*, utils::hashbrown::HashMap};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, sync::OnceLock};
+
+pub struct ItemPlugin;
+impl Plugin for ItemPlugin {
+    fn name(&self) -> &str {
+        "Item Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<Items>::new(&["items.ron"]))
+            .add_plugins(RonAssetPlugin::<LootTables>::new(&["loot_tables.ron"]))
+            .add_event::<CollectItem>()
+            .add_event::<StoreItem>()
+            .add_event::<EquipItem>()
+            .add_systems(Update, evr_collect_item.run_if(in_state(AppState::Playing)));
+    }
+}
+
+// GUARDS AGAINST LOOT TABLES THAT POINT BACK AT THEMSELVES
+pub const MAX_LOOT_DEPTH: usize = 8;
+
+#[derive(Asset, TypePath, Resource, Clone, Default, Deserialize, Serialize)]
+pub struct Items(pub Vec<Item>);
+impl Items {
+    pub fn get(&self, id: ItemID) -> Option<&Item> {
+        self.0.iter().find(|item| *item.id.0 == *id.0)
+    }
+
+    pub fn for_weapon(&self, wid: WeaponID) -> Option<&Item> {
+        self.0
+            .iter()
+            .find(|item| matches!(item.kind, ItemKind::WEAPON(weapon) if *weapon.0 == *wid.0))
+    }
+}
+impl Display for Items {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut string: String = String::new();
+        for item in self.0.iter() {
+            string.push_str(&item.title);
+            string.push_str(&", ");
+        }
+
+        write!(f, "{}", string)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Item {
+    pub id: ItemID,
+    pub title: Title,
+    pub kind: ItemKind,
+    pub sprite: String, // ItemAssets key
+    pub max_stack: u32,
+}
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+pub enum ItemKind {
+    #[default]
+    CURRENCY,
+    CONSUMABLE,
+    MATERIAL,
+    WEAPON(WeaponID),
+}
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CURRENCY => write!(f, "Currency"),
+            Self::CONSUMABLE => write!(f, "Consumable"),
+            Self::MATERIAL => write!(f, "Material"),
+            Self::WEAPON(_) => write!(f, "Weapon"),
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Default, Deserialize, Serialize, Deref, DerefMut)]
+pub struct ItemID(pub ID);
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+pub struct ItemStack {
+    pub item: ItemID,
+    pub quantity: u32,
+}
+
+#[derive(Asset, TypePath, Resource, Clone, Default, Deserialize, Serialize)]
+pub struct LootTables(pub Vec<LootTable>);
+impl LootTables {
+    pub fn get(&self, id: usize) -> Option<&LootTable> {
+        self.0.iter().find(|table| table.id == id)
+    }
+
+    // RESOLVES A TABLE (AND ANY TABLES IT POINTS TO) INTO ITEM STACKS
+    pub fn roll(&self, id: usize, rng: &mut GameRng) -> Vec<ItemStack> {
+        let mut drops = Vec::new();
+        self.roll_into(id, rng.stream(ChanceKind::LOOT), 0, &mut drops);
+        drops
+    }
+
+    fn roll_into(&self, id: usize, rng: &mut impl Rng, depth: usize, drops: &mut Vec<ItemStack>) {
+        if depth >= MAX_LOOT_DEPTH {
+            warn!("[SKIPPED] Loot Table {} (nested too deep)", id);
+            return;
+        }
+        let Some(table) = self.get(id) else {
+            warn!("[SKIPPED] Loot Table {} (unknown)", id);
+            return;
+        };
+        let Some(entry) = table.pick(rng) else {
+            return;
+        };
+        match entry.drop {
+            LootDrop::NOTHING => {}
+            LootDrop::ITEM { id, min, max } => {
+                let quantity = rng.gen_range(min.min(max)..=max.max(min));
+                if quantity > 0 {
+                    drops.push(ItemStack { item: id, quantity });
+                }
+            }
+            LootDrop::TABLE(table) => self.roll_into(table, rng, depth + 1, drops),
+        }
+    }
+}
+impl Display for LootTables {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut string: String = String::new();
+        for table in self.0.iter() {
+            string.push_str(&table.title);
+            string.push_str(&", ");
+        }
+
+        write!(f, "{}", string)
+    }
+}
+
+// ids are the keys of weighting_loot and enemy drop pools
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct LootTable {
+    pub id: usize,
+    pub title: Title,
+    // private so it can't change under the alias table built from it
+    entries: Vec<LootEntry>,
+    // built from the entry weights on first pick, keyed by entry index
+    #[serde(skip)]
+    alias: OnceLock<Option<AliasTable>>,
+}
+impl LootTable {
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<&LootEntry> {
+        self.alias
+            .get_or_init(|| {
+                let pool: HashMap<usize, f32> = self
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, entry)| (i, entry.weight))
+                    .collect();
+                AliasTable::new(&pool)
+            })
+            .as_ref()
+            .map(|table| &self.entries[table.sample(rng)])
+    }
+}
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+pub struct LootEntry {
+    pub weight: f32,
+    pub drop: LootDrop,
+}
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+pub enum LootDrop {
+    #[default]
+    NOTHING,
+    ITEM {
+        id: ItemID,
+        min: u32,
+        max: u32,
+    },
+    TABLE(usize),
+}
+
+#[derive(Event)]
+pub struct CollectItem(pub ItemStack);
+
+// consumables and materials headed for the party's inventory
+#[derive(Event)]
+pub struct StoreItem(pub ItemStack);
+
+// weapons headed for the party's equipment
+#[derive(Event)]
+pub struct EquipItem {
+    pub item: ItemID,
+    pub weapon: WeaponID,
+}
+
+// ROUTES EACH COLLECTED ITEM TO THE WALLET, INVENTORY OR EQUIPMENT
+pub fn evr_collect_item(
+    mut evr_collect_item: EventReader<CollectItem>,
+    mut evw_store_item: EventWriter<StoreItem>,
+    mut evw_equip_item: EventWriter<EquipItem>,
+    mut player_loot: ResMut<PlayerLoot>,
+    items: Res<Items>,
+) {
+    for ev in evr_collect_item.read() {
+        let stack = ev.0;
+        let Some(item) = items.get(stack.item) else {
+            warn!("[SKIPPED] Item {} (unknown)", *stack.item.0);
+            continue;
+        };
+        match item.kind {
+            ItemKind::CURRENCY => **player_loot += stack.quantity as i32,
+            ItemKind::CONSUMABLE | ItemKind::MATERIAL => {
+                evw_store_item.send(StoreItem(stack));
+            }
+            ItemKind::WEAPON(weapon) => {
+                for _ in 0..stack.quantity {
+                    evw_equip_item.send(EquipItem {
+                        item: stack.item,
+                        weapon,
+                    });
+                }
+            }
+        }
+        info!(
+            "[COLLECTED] {} x{} ({})",
+            *item.title, stack.quantity, item.kind
+        );
+    }
+}
@@ -1,28 +1,62 @@
 mod area;
+mod audio;
+mod autosave;
 mod background;
+mod camera;
 mod chance;
 mod character;
+mod combat;
+mod damage;
 mod encounter;
+mod enemy;
+mod equipment;
+mod game_over;
+mod health;
+mod hud;
+mod inventory;
+mod inventory_menu;
+mod item;
 mod loading;
+mod notification;
+mod pause;
 mod player;
 mod save;
 mod settings;
+mod slot_menu;
+mod theme;
 mod ui;
 mod weapon;
 
 use area::AreaPlugin;
+use audio::SoundPlugin;
+use autosave::AutosavePlugin;
 use background::BackgroundPlugin;
-use bevy::{
-    prelude::*,
-    window::{WindowResized, WindowTheme},
-};
+use bevy::{prelude::*, window::WindowTheme};
+use camera::CameraPlugin;
+use chance::ChancePlugin;
 use character::CharacterPlugin;
+use combat::CombatPlugin;
+use damage::DamagePlugin;
 use encounter::EncounterPlugin;
+use enemy::EnemyPlugin;
+use equipment::EquipmentPlugin;
+use game_over::GameOverPlugin;
+use health::HealthPlugin;
+use hud::HudPlugin;
+use inventory::InventoryPlugin;
+use inventory_menu::InventoryMenuPlugin;
+use item::ItemPlugin;
 use loading::LoadingPlugin;
+use notification::NotificationPlugin;
+use pause::PausePlugin;
 use player::PlayerPlugin;
 use save::SavePlugin;
+// INSERT BEFORE GamePlugin TO REDIRECT SETTINGS AND SAVES, E.G. INTO A TEMP DIR FOR TESTS
+pub use save::UserDirectory;
 use serde::{Deserialize, Serialize};
-use settings::{Settings, SettingsPlugin};
+use settings::SettingsPlugin;
+use slot_menu::SlotMenuPlugin;
+use theme::ThemePlugin;
 use ui::MenuPlugin;
 use weapon::WeaponPlugin;
 
@@ -39,7 +73,7 @@ impl Plugin for GamePlugin {
                         fit_canvas_to_parent: true,
                         prevent_default_event_handling: true,
                         window_theme: Some(WindowTheme::Dark),
-                        resizable: false,
+                        resizable: false, // settings apply their own on startup
                         ..default()
                     }
                     .into(),
@@ -54,16 +88,37 @@ impl Plugin for GamePlugin {
                 .set(ImagePlugin::default_nearest()),
         );
         app.add_plugins((
+            CameraPlugin,
+            SoundPlugin,
             MenuPlugin,
             LoadingPlugin,
+            SavePlugin,
+            AutosavePlugin,
+            SettingsPlugin,
+            ThemePlugin,
+            SlotMenuPlugin,
+            InventoryMenuPlugin,
+            ChancePlugin,
+            GameOverPlugin,
+            PausePlugin,
+            NotificationPlugin,
+        ));
+        // GAMEPLAY
+        app.add_plugins((
             BackgroundPlugin,
             PlayerPlugin,
             CharacterPlugin,
+            CombatPlugin,
+            DamagePlugin,
             AreaPlugin,
             EncounterPlugin,
-            SavePlugin,
+            EnemyPlugin,
+            EquipmentPlugin,
+            HealthPlugin,
+            HudPlugin,
+            InventoryPlugin,
+            ItemPlugin,
             WeaponPlugin,
-            SettingsPlugin,
         ));
         // MIGHT BE A TOGGLEABLE SETTING
         //app.insert_resource(Msaa::Off); // bevy 15 migration
@@ -75,121 +130,83 @@ impl Plugin for GamePlugin {
         app.init_resource::<SpawnLocations>();
         app.init_state::<AppState>();
         app.add_sub_state::<GameState>();
-        app.add_systems(Startup, setup);
-        app.add_systems(Update, initialize_spawn_locations);
-    }
-}
-
-// SETUP
-
-fn setup(mut commands: Commands, mut query_window: Query<&mut Window>, settings: Res<Settings>) {
-    // SPAWN CAMERA2D
-    commands.spawn(Camera2d);
-
-    // SET WINDOW RESOLUTION ACCORDING TO SAVED SETTING
-    if let Ok(mut window) = query_window.get_single_mut() {
-        // SET WINDOW RESOLUTION
-        window
-            .resolution
-            .set(settings.resolution.x, settings.resolution.y);
-        // SET MONITOR SELECTION
-        window
-            .position
-            .center(MonitorSelection::Index(settings.monitor));
-        info!(
-            "[MODIFIED] Window Resolution : ({},{})",
-            settings.resolution.x, settings.resolution.y
+        app.add_sub_state::<PauseState>();
+        app.configure_sets(
+            OnEnter(AppState::Playing),
+            (RunSet::Reset, RunSet::Restore).chain(),
         );
     }
 }
 
-// SET SPAWN COORDINATES ACCORDING TO RESOLUTION
-// THIS RUNS WHENEVER SCREEN IS RESIZED
-fn initialize_spawn_locations(
-    mut evr_window_resized: EventReader<WindowResized>,
-    mut spawn_locations: ResMut<SpawnLocations>,
-) {
-    for ev in evr_window_resized.read() {
-        let x = ev.width;
-        let middle = 0.;
-        let lane_one = 0.;
-        let lane_two = 200.;
-        let lane_three = -200.;
-        let character_one = Vec3::new(middle - (x / 2.) + 200., lane_one, CHARACTER_LAYER);
-        let character_two = Vec3::new(middle - (x / 2.) + 200., lane_two, CHARACTER_LAYER);
-        let character_three = Vec3::new(middle - (x / 2.) + 200., lane_three, CHARACTER_LAYER);
-        let characters_array = [character_one, character_two, character_three];
-        spawn_locations.characters = characters_array;
-        let background_gap = 320.;
-        let backgrounds_one = Vec3::new(middle, middle, BACKGROUND_LAYER);
-        let backgrounds_two = Vec3::new(middle - background_gap, middle, BACKGROUND_LAYER);
-        let backgrounds_three = Vec3::new(middle - (background_gap * 2.), middle, BACKGROUND_LAYER);
-        let backgrounds_four = Vec3::new(middle - (background_gap * 3.), middle, BACKGROUND_LAYER);
-        let backgrounds_five = Vec3::new(middle + background_gap, middle, BACKGROUND_LAYER);
-        let backgrounds_six = Vec3::new(middle + (background_gap * 2.), middle, BACKGROUND_LAYER);
-        let backgrounds_seven = Vec3::new(middle + (background_gap * 3.), middle, BACKGROUND_LAYER);
-        let backgrounds_eight = Vec3::new(middle + (background_gap * 4.), middle, BACKGROUND_LAYER);
-        let backgrounds_nine = Vec3::new(
-            middle + (background_gap * 4.5) - 2., // 2 pixels underlay to prevent bg gaps
-            middle,
-            BACKGROUND_LAYER,
-        );
-        let backgrounds_array = [
-            backgrounds_one,
-            backgrounds_two,
-            backgrounds_three,
-            backgrounds_four,
-            backgrounds_five,
-            backgrounds_six,
-            backgrounds_seven,
-            backgrounds_eight,
-            backgrounds_nine,
-        ];
-        spawn_locations.backgrounds = backgrounds_array;
-        let encounter_one = Vec3::new(x + 100., lane_one, ENCOUNTER_LAYER);
-        let encounter_two = Vec3::new(x + 100., lane_two, ENCOUNTER_LAYER);
-        let encounter_three = Vec3::new(x + 100., lane_three, ENCOUNTER_LAYER);
-        let encounter_array = [encounter_one, encounter_two, encounter_three];
-        spawn_locations.encounters = encounter_array;
-        let despawn_left = -(ev.width / 2.) - (background_gap / 2.);
-        let despawn_right = (ev.width * 2.) + (background_gap / 2.);
-        let despawn_array = [despawn_left, despawn_right];
-        spawn_locations.despawns = despawn_array;
-        info!("[INITIALIZED] [RESOURCE] Spawn Locations");
-    }
-}
-
 // GLOBAL CONSTANTS
 
-pub const CHARACTER_SCALE: f32 = 8.;
+// NOTE: SCALES AND POSITIONS ARE IN VIRTUAL PIXELS, SEE camera
+pub const VIRTUAL_RESOLUTION: Vec2 = Vec2::new(384., 216.);
+pub const CHARACTER_SCALE: f32 = 2.;
 pub const CHARACTER_LAYER: f32 = 2.;
-pub const ENCOUNTER_SCALE: f32 = 4.;
+pub const ENCOUNTER_SCALE: f32 = 1.;
 pub const ENCOUNTER_LAYER: f32 = 1.;
-pub const BACKGROUND_SCALE: f32 = 5.;
+pub const BACKGROUND_SCALE: f32 = 1.;
 pub const BACKGROUND_LAYER: f32 = 0.;
+pub const SCROLL_SPEED: f32 = 1.; // distance per frame
+pub const DISTANCE_SCALE: f32 = 0.2; // virtual pixels per unit of distance
 pub const CLEARCOLOR: (f32, f32, f32) = (0.1, 0.1, 0.1);
 
-pub const BUTTON_BACKGROUND_COLOR_DEFAULT: Color = Color::srgb(211., 211., 211.);
-pub const BUTTON_BACKGROUND_COLOR_HOVERED: Color = Color::srgb(167., 167., 167.);
-pub const BUTTON_BACKGROUND_COLOR_PRESSED: Color = Color::srgb(54., 54., 54.);
-
-pub const BUTTON_TEXT_COLOR_DEFAULT: Color = Color::srgb(54., 54., 54.);
-pub const BUTTON_TEXT_COLOR_HOVERED: Color = Color::srgb(93., 93., 93.);
-pub const BUTTON_TEXT_COLOR_PRESSED: Color = Color::WHITE;
-
-pub const BUTTON_BORDER_COLOR_DEFAULT: Color = Color::srgb(54., 54., 54.);
-pub const BUTTON_BORDER_COLOR_HOVERED: Color = Color::srgb(93., 93., 93.);
-pub const BUTTON_BORDER_COLOR_PRESSED: Color = Color::WHITE;
-
 // GLOBAL RESOURCES
 
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct SpawnLocations {
     characters: [Vec3; 3],
     backgrounds: [Vec3; 9],
     encounters: [Vec3; 3],
     despawns: [f32; 2],
 }
+// THE VIRTUAL RESOLUTION NEVER CHANGES SO NEITHER DO THESE
+impl Default for SpawnLocations {
+    fn default() -> Self {
+        let x = VIRTUAL_RESOLUTION.x;
+        let middle = 0.;
+        let lane_one = 0.;
+        let lane_two = 40.;
+        let lane_three = -40.;
+        let party_x = middle - (x / 2.) + 40.;
+        let characters = [
+            Vec3::new(party_x, lane_one, CHARACTER_LAYER),
+            Vec3::new(party_x, lane_two, CHARACTER_LAYER),
+            Vec3::new(party_x, lane_three, CHARACTER_LAYER),
+        ];
+        let background_gap = 64.;
+        let backgrounds = [
+            Vec3::new(middle, middle, BACKGROUND_LAYER),
+            Vec3::new(middle - background_gap, middle, BACKGROUND_LAYER),
+            Vec3::new(middle - (background_gap * 2.), middle, BACKGROUND_LAYER),
+            Vec3::new(middle - (background_gap * 3.), middle, BACKGROUND_LAYER),
+            Vec3::new(middle + background_gap, middle, BACKGROUND_LAYER),
+            Vec3::new(middle + (background_gap * 2.), middle, BACKGROUND_LAYER),
+            Vec3::new(middle + (background_gap * 3.), middle, BACKGROUND_LAYER),
+            Vec3::new(middle + (background_gap * 4.), middle, BACKGROUND_LAYER),
+            Vec3::new(
+                middle + (background_gap * 4.5) - 1., // 1 pixel underlay to prevent bg gaps
+                middle,
+                BACKGROUND_LAYER,
+            ),
+        ];
+        let encounter_x = x + 20.;
+        let encounters = [
+            Vec3::new(encounter_x, lane_one, ENCOUNTER_LAYER),
+            Vec3::new(encounter_x, lane_two, ENCOUNTER_LAYER),
+            Vec3::new(encounter_x, lane_three, ENCOUNTER_LAYER),
+        ];
+        let despawn_left = -(x / 2.) - (background_gap / 2.);
+        let despawn_right = (x * 2.) + (background_gap / 2.);
+        Self {
+            characters,
+            backgrounds,
+            encounters,
+            despawns: [despawn_left, despawn_right],
+        }
+    }
+}
 
 // GLOBAL STATES
 
@@ -199,7 +216,9 @@ pub enum AppState {
     Loading,
     Menu,
     Playing,
+    SaveSlots,
     Settings,
+    GameOver,
     Exit,
 }
 
@@ -211,6 +230,26 @@ pub enum GameState {
     Combat,
 }
 
+// separate from GameState so a paused fight resumes as a fight
+#[derive(SubStates, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[source(AppState = AppState::Playing)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+    Settings,
+    Inventory,
+}
+
+// GLOBAL SYSTEM SETS
+
+// ORDERS ONENTER(PLAYING): FRESH RUN DEFAULTS FIRST, THEN ANY SAVED RUN ON TOP
+#[derive(SystemSet, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum RunSet {
+    Reset,
+    Restore,
+}
+
 // GLOBAL COMPONENTS
 
 #[derive(Component, Clone, Default, Deref, DerefMut, Deserialize, Serialize)]
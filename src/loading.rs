@@ -1,6 +1,20 @@
-use crate::AppState;
-use bevy::prelude::*;
+use crate::{
+    area::Areas,
+    character::Characters,
+    damage::DamageCoefficients,
+    encounter::EncounterSchedule,
+    enemy::Enemies,
+    item::{Items, LootTables},
+    weapon::Weapons,
+    AppState,
+};
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
 use bevy_asset_loader::prelude::*;
+use serde::de::DeserializeOwned;
+use std::{fmt::Display, marker::PhantomData};
 
 pub struct LoadingPlugin;
 
@@ -17,11 +31,126 @@ impl Plugin for LoadingPlugin {
                 .load_collection::<BackgroundAssets>()
                 .load_collection::<CharacterAssets>()
                 .load_collection::<ItemAssets>()
-                .load_collection::<IconAssets>(),
+                .load_collection::<IconAssets>()
+                .load_collection::<DataAssets>(),
         );
     }
 }
 
+// REGISTERS A READ-ONLY RON DATA FILE AS AN ASSET AND COPIES IT INTO A RESOURCE ONCE LOADED
+pub struct RonAssetPlugin<A> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<A>,
+}
+impl<A> RonAssetPlugin<A> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _marker: PhantomData,
+        }
+    }
+}
+impl<A: Asset + Resource + Default + DeserializeOwned> Plugin for RonAssetPlugin<A> {
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn build(&self, app: &mut App) {
+        app.init_asset::<A>()
+            .init_resource::<A>()
+            .register_asset_loader(RonAssetLoader::<A> {
+                extensions: self.extensions,
+                _marker: PhantomData,
+            })
+            .add_systems(OnExit(AppState::Loading), insert_data::<A>);
+    }
+}
+
+struct RonAssetLoader<A> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<A>,
+}
+impl<A: Asset + DeserializeOwned> AssetLoader for RonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<A, RonLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
+
+#[derive(Debug)]
+pub enum RonLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Invalid(String), // parsed but unusable, e.g. a missing dependency
+}
+impl From<std::io::Error> for RonLoaderError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+impl From<ron::error::SpannedError> for RonLoaderError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::Ron(e)
+    }
+}
+impl Display for RonLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Could not read file: {}", e),
+            Self::Ron(e) => write!(f, "Could not deserialize: {}", e),
+            Self::Invalid(e) => write!(f, "Could not build asset: {}", e),
+        }
+    }
+}
+impl std::error::Error for RonLoaderError {}
+
+// THE ASSET IS MOVED OUT, ONLY THE RESOURCE IS READ AFTER LOADING
+fn insert_data<A: Asset + Resource>(mut commands: Commands, mut assets: ResMut<Assets<A>>) {
+    let Some(id) = assets.ids().next() else {
+        error!("[ERROR] Missing data asset {}", A::short_type_path());
+        return;
+    };
+    if let Some(data) = assets.remove(id) {
+        commands.insert_resource(data);
+        info!("[INITIALIZED] {}", A::short_type_path());
+    }
+}
+
+// read-only game data, extensions must stay unique per type so untyped loads pick the right loader
+#[derive(AssetCollection, Resource)]
+pub struct DataAssets {
+    #[asset(path = "data/base.areas.ron")]
+    pub areas: Handle<Areas>,
+    #[asset(path = "data/base.characters.ron")]
+    pub characters: Handle<Characters>,
+    #[asset(path = "data/base.damage.ron")]
+    pub damage: Handle<DamageCoefficients>,
+    #[asset(path = "data/base.encounter_schedule.ron")]
+    pub encounter_schedule: Handle<EncounterSchedule>,
+    #[asset(path = "data/base.enemies.ron")]
+    pub enemies: Handle<Enemies>,
+    #[asset(path = "data/base.items.ron")]
+    pub items: Handle<Items>,
+    #[asset(path = "data/base.loot_tables.ron")]
+    pub loot_tables: Handle<LootTables>,
+    #[asset(path = "data/base.weapons.ron")]
+    pub weapons: Handle<Weapons>,
+}
+
 #[derive(AssetCollection, Resource)]
 pub struct AudioAssets {}
 
@@ -30,6 +159,15 @@ pub struct CharacterAssets {
     #[asset(key = "character_old_man_0")]
     pub character_old_man_0: Handle<Image>,
 }
+impl CharacterAssets {
+    // RESOLVES A SPRITE KEY FROM RON DATA TO ITS LOADED HANDLE
+    pub fn get(&self, key: &str) -> Option<Handle<Image>> {
+        match key {
+            "character_old_man_0" => Some(self.character_old_man_0.clone()),
+            _ => None,
+        }
+    }
+}
 
 #[derive(AssetCollection, Resource)]
 pub struct BackgroundAssets {
@@ -52,6 +190,15 @@ pub struct ItemAssets {
     #[asset(key = "item_money_0")]
     pub item_money_0: Handle<Image>,
 }
+impl ItemAssets {
+    // RESOLVES A SPRITE KEY FROM RON DATA TO ITS LOADED HANDLE
+    pub fn get(&self, key: &str) -> Option<Handle<Image>> {
+        match key {
+            "item_money_0" => Some(self.item_money_0.clone()),
+            _ => None,
+        }
+    }
+}
 
 #[derive(AssetCollection, Resource)]
 pub struct IconAssets {
@@ -0,0 +1,107 @@
+use crate::theme::{ColorRole, FontRole, Theme, ThemedTextColor};
+use bevy::prelude::*;
+
+pub struct NotificationPlugin;
+impl Plugin for NotificationPlugin {
+    fn name(&self) -> &str {
+        "Notification Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_event::<Notify>()
+            .add_systems(Startup, spawn_notification_root)
+            .add_systems(Update, (evr_notify, expire_notifications).chain());
+    }
+}
+
+// SECONDS A NOTIFICATION STAYS ON SCREEN
+pub const NOTIFICATION_DURATION: f32 = 4.;
+
+// top right column the notifications stack in, lives for the whole app
+#[derive(Component, Clone, Copy)]
+pub struct NotificationRoot;
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Notification(pub Timer);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum NotifyKind {
+    Info,
+    Error,
+}
+
+#[derive(Event, Clone)]
+pub struct Notify {
+    pub kind: NotifyKind,
+    pub message: String,
+}
+impl Notify {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            kind: NotifyKind::Info,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            kind: NotifyKind::Error,
+            message: message.into(),
+        }
+    }
+}
+
+fn spawn_notification_root(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.),
+            right: Val::Px(10.),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: Val::Px(5.),
+            ..default()
+        },
+        // ABOVE EVERY SCREEN
+        GlobalZIndex(i32::MAX),
+        NotificationRoot,
+    ));
+}
+
+pub fn evr_notify(
+    mut commands: Commands,
+    mut evr_notify: EventReader<Notify>,
+    theme: Res<Theme>,
+    query_root: Query<Entity, With<NotificationRoot>>,
+) {
+    let Ok(root) = query_root.get_single() else {
+        return;
+    };
+    for ev in evr_notify.read() {
+        let role = match ev.kind {
+            NotifyKind::Info => ColorRole::Info,
+            NotifyKind::Error => ColorRole::Error,
+        };
+        commands.entity(root).with_child((
+            Text::new(ev.message.clone()),
+            theme.text_font(FontRole::Small),
+            TextColor(theme.color(role)),
+            ThemedTextColor(role),
+            Notification(Timer::from_seconds(NOTIFICATION_DURATION, TimerMode::Once)),
+        ));
+        info!("[EVENT] [READ] Notify: {}", ev.message);
+    }
+}
+
+// REAL TIME SO TOASTS STILL EXPIRE WHILE A RUN IS PAUSED
+fn expire_notifications(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut query_notification: Query<(Entity, &mut Notification)>,
+) {
+    for (entity, mut timer) in query_notification.iter_mut() {
+        if timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
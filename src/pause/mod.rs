@@ -0,0 +1,115 @@
+use crate::{
+    save::Save,
+    theme::{ColorRole, Theme},
+    ui::{MenuAction, MenuAppExt, MenuBuilder, Screen, Screens},
+    AppState, PauseState,
+};
+use bevy::prelude::*;
+
+pub struct PausePlugin;
+impl Plugin for PausePlugin {
+    fn name(&self) -> &str {
+        "Pause Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_menu_action::<PauseMenuButton>()
+            .add_systems(OnExit(PauseState::Running), pause_time)
+            .add_systems(OnEnter(PauseState::Running), unpause_time)
+            // SAVE & QUIT LEAVES THE RUN WITHOUT PASSING THROUGH Running
+            .add_systems(OnExit(AppState::Playing), unpause_time)
+            .add_systems(OnEnter(PauseState::Paused), spawn_pause_menu)
+            .add_systems(
+                Update,
+                toggle_pause.run_if(in_state(PauseState::Running).or(in_state(PauseState::Paused))),
+            )
+            .add_systems(Update, evr_pause_menu.run_if(in_state(PauseState::Paused)))
+            .add_systems(OnExit(PauseState::Paused), despawn_pause_menu);
+    }
+}
+
+// attached to all pause menu components for cleanup
+#[derive(Component, Clone, Copy)]
+pub struct CleanupPauseMenu;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum PauseMenuButton {
+    Resume,
+    Inventory,
+    Settings,
+    SaveAndQuit,
+}
+
+// FREEZES EVERY Time DRIVEN TIMER, E.G. COMBAT TURNS AND AUTOSAVE
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+    info!("[MODIFIED] Time >> Paused");
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    if time.is_paused() {
+        time.unpause();
+        info!("[MODIFIED] Time >> Running");
+    }
+}
+
+fn toggle_pause(keys: Res<ButtonInput<KeyCode>>, gamepads: Query<&Gamepad>, mut screens: Screens) {
+    let pressed = keys.just_pressed(KeyCode::Escape)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::Start));
+    if !pressed {
+        return;
+    }
+    match screens.current() {
+        Screen::App(_) => screens.push(PauseState::Paused),
+        _ => screens.goto(PauseState::Running),
+    }
+}
+
+fn spawn_pause_menu(mut commands: Commands, theme: Res<Theme>) {
+    let root = MenuBuilder::new()
+        .title("PAUSED")
+        .row([
+            ("RESUME", PauseMenuButton::Resume),
+            ("INVENTORY", PauseMenuButton::Inventory),
+            ("SETTINGS", PauseMenuButton::Settings),
+            ("SAVE & QUIT", PauseMenuButton::SaveAndQuit),
+        ])
+        .button_width(260.)
+        .spawn(&mut commands, &theme, CleanupPauseMenu);
+    commands
+        .entity(root)
+        .insert((theme.background(ColorRole::Overlay), GlobalZIndex(1)));
+    info!("[SPAWNED] Pause Menu Entities");
+}
+
+fn despawn_pause_menu(
+    mut commands: Commands,
+    query_pause_menu: Query<Entity, With<CleanupPauseMenu>>,
+) {
+    for entity in query_pause_menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("[DESPAWNED] Pause Menu Entities.");
+}
+
+fn evr_pause_menu(
+    mut evr_menu_action: EventReader<MenuAction<PauseMenuButton>>,
+    mut evw_save: EventWriter<Save>,
+    mut screens: Screens,
+) {
+    for ev in evr_menu_action.read() {
+        match **ev {
+            PauseMenuButton::Resume => screens.goto(PauseState::Running),
+            PauseMenuButton::Inventory => screens.push(PauseState::Inventory),
+            PauseMenuButton::Settings => screens.push(PauseState::Settings),
+            // THE RUN IS SNAPSHOTTED ON LEAVING Playing AND WRITTEN BEFORE THE MAIN MENU IS BUILT
+            PauseMenuButton::SaveAndQuit => {
+                evw_save.send(Save);
+                info!("[EVENT] [WRITE] Save Game (Quit)");
+                screens.goto(AppState::Menu);
+            }
+        }
+    }
+}
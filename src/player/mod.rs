@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 
 use crate::{
-    character::CharacterBundle, loading::CharacterAssets, AppState, SpawnLocations, CHARACTER_SCALE,
+    character::{CharacterBundle, Characters},
+    health::Health,
+    loading::CharacterAssets,
+    save::{PendingRestore, SavedCharacter},
+    AppState, GameState, PauseState, RunSet, SpawnLocations, CHARACTER_SCALE, SCROLL_SPEED,
 };
 
 pub struct PlayerPlugin;
@@ -9,21 +13,44 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerLoot>()
+            .init_resource::<PlayerKills>()
+            .init_resource::<PlayerDistance>()
+            .init_resource::<PlayerTime>()
             .add_event::<SpawnPlayer>()
             .add_systems(
                 OnEnter(AppState::Playing),
-                (evw_spawn_player, spawn_money_info),
+                (
+                    reset_player_run,
+                    evw_spawn_player.run_if(not(resource_exists::<PendingRestore>)),
+                )
+                    .in_set(RunSet::Reset),
             )
             .add_systems(
                 Update,
-                (evr_spawn_player, update_loot_info).run_if(in_state(AppState::Playing)),
-            );
+                (evr_spawn_player, track_play_time).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                track_distance
+                    .run_if(in_state(GameState::Home))
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_systems(OnExit(AppState::Playing), despawn_player);
     }
 }
 
 #[derive(Resource, Default, Deref, DerefMut)]
 pub struct PlayerLoot(pub i32);
 
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PlayerKills(pub u32);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PlayerDistance(pub f32);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PlayerTime(pub f32); // seconds
+
 #[derive(Bundle, Default)]
 pub struct PlayerBundle {
     player: Player,
@@ -33,94 +60,98 @@ pub struct PlayerBundle {
 #[derive(Component, Clone, Default)]
 pub struct Player;
 
-#[derive(Component, Clone, Default)]
-pub struct MoneyCountText;
+// the party position a player stands in, an index into SpawnLocations::characters
+#[derive(Component, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deref)]
+pub struct Lane(pub usize);
 
-#[derive(Event, Deref)]
-pub struct SpawnPlayer(pub usize);
+#[derive(Event)]
+pub struct SpawnPlayer {
+    pub lane: usize,
+    pub saved: Option<SavedCharacter>, // restored party member, fresh character when None
+}
 
 pub fn evr_spawn_player(
     mut commands: Commands,
     mut evr_spawn_player: EventReader<SpawnPlayer>,
     character_assets: Res<CharacterAssets>,
     spawn_locations: Res<SpawnLocations>,
+    characters: Res<Characters>,
 ) {
     for ev in evr_spawn_player.read() {
-        commands.spawn((
-            PlayerBundle::default(),
-            Sprite::from_image(character_assets.character_old_man_0.clone()),
-            Transform {
-                translation: spawn_locations.characters[**ev],
-                scale: Vec3::splat(CHARACTER_SCALE),
-                ..default()
-            },
-            // BEVY 15 MIGRATION
-            //SpriteBundle {
-            //texture: character_assets.character_old_man_0.clone(),
-            //transform: Transform {
-            //translation: spawn_locations.characters[**ev],
-            //scale: Vec3::splat(CHARACTER_SCALE),
-            //..default()
-            //},
-            //..default()
-            //},
-        ));
-        info!("[EVENT] [READ] SpawnPlayer({})", **ev);
+        let character = match &ev.saved {
+            Some(saved) => saved.character.clone(),
+            None => characters.0.first().cloned().unwrap_or_default(),
+        };
+        let health = match &ev.saved {
+            Some(saved) => saved.health,
+            None => Health::from_constitution(&character.constitution),
+        };
+        let entity = commands
+            .spawn((
+                PlayerBundle {
+                    character,
+                    ..default()
+                },
+                health,
+                Lane(ev.lane),
+                Sprite::from_image(character_assets.character_old_man_0.clone()),
+                Transform {
+                    translation: spawn_locations.characters[ev.lane],
+                    scale: Vec3::splat(CHARACTER_SCALE),
+                    ..default()
+                },
+                // BEVY 15 MIGRATION
+                //SpriteBundle {
+                //texture: character_assets.character_old_man_0.clone(),
+                //transform: Transform {
+                //translation: spawn_locations.characters[**ev],
+                //scale: Vec3::splat(CHARACTER_SCALE),
+                //..default()
+                //},
+                //..default()
+                //},
+            ))
+            .id();
+        // RESTORED EQUIPMENT SKIPS THE DEFAULT WEAPON
+        if let Some(saved) = &ev.saved {
+            commands.entity(entity).insert(saved.equipment);
+        }
+        info!("[EVENT] [READ] SpawnPlayer({})", ev.lane);
     }
 }
 
 fn evw_spawn_player(mut evw_spawn_player: EventWriter<SpawnPlayer>) {
-    evw_spawn_player.send(SpawnPlayer(0));
+    evw_spawn_player.send(SpawnPlayer {
+        lane: 0,
+        saved: None,
+    });
     info!("[EVENT] [WRITE] SpawnPlayer({})", 0);
 }
 
-pub fn spawn_money_info(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn((
-        Text::new("Money: "),
-        TextFont {
-            font: asset_server.load("fonts/PixelifySans-Regular.ttf"),
-            font_size: 100.,
-            ..default()
-        },
-        TextLayout::new_with_justify(JustifyText::Center),
-        Node {
-            position_type: PositionType::Absolute,
-            bottom: Val::Px(10.),
-            left: Val::Px(10.),
-            ..default()
-        },
-        MoneyCountText,
-    ));
-    // BEVY 15 MIGRATION
-    //let font = asset_server.load("fonts/PixelifySans-Regular.ttf");
-    // let style = TextStyle {
-    //     font,
-    //     font_size: 100.,
-    //     ..default()
-    // };
-    // commands.spawn(Text2dBundle {
-    //     text: Text::from_section("LOOT: ", style.clone()).with_justify(JustifyText::Center),
-    //     transform: Transform::from_xyz(-800., -400., 5.),
-    //     ..default()
-    // });
-    //
-    // commands.spawn((
-    //     Text2dBundle {
-    //         text: Text::from_section("", style).with_justify(JustifyText::Right),
-    //         transform: Transform::from_xyz(-650., -400., 5.),
-    //         ..default()
-    //     },
-    //     LootCountText,
-    // ));
+// CLEARS THE PREVIOUS RUN'S TALLIES
+fn reset_player_run(
+    mut player_loot: ResMut<PlayerLoot>,
+    mut player_kills: ResMut<PlayerKills>,
+    mut player_distance: ResMut<PlayerDistance>,
+    mut player_time: ResMut<PlayerTime>,
+) {
+    **player_loot = 0;
+    **player_kills = 0;
+    **player_distance = 0.;
+    **player_time = 0.;
 }
 
-pub fn update_loot_info(
-    mut query_text: Query<&mut TextSpan, With<MoneyCountText>>,
-    player_loot: Res<PlayerLoot>,
-) {
-    if player_loot.is_changed() {
-        for mut text in &mut query_text {
-            **text = format!("{}", **player_loot);
-        }
+pub fn track_distance(mut player_distance: ResMut<PlayerDistance>) {
+    **player_distance += SCROLL_SPEED;
+}
+
+pub fn track_play_time(time: Res<Time>, mut player_time: ResMut<PlayerTime>) {
+    **player_time += time.delta_secs();
+}
+
+fn despawn_player(mut commands: Commands, query_player: Query<Entity, With<Player>>) {
+    for entity in query_player.iter() {
+        commands.entity(entity).despawn_recursive();
     }
+    info!("[DESPAWNED] Player Entities");
 }
@@ -1,11 +1,29 @@
-use crate::{area::Area, AppState};
-use bevy::prelude::*;
+use crate::{
+    area::{Area, AreaKind, CurrentArea},
+    chance::{Chance, ChanceKind, GameRng, Weighting},
+    character::{CharacterBundle, CharacterQuery},
+    equipment::Equipment,
+    health::Health,
+    inventory::Inventory,
+    notification::Notify,
+    player::{Lane, Player, PlayerDistance, PlayerKills, PlayerLoot, PlayerTime, SpawnPlayer},
+    AppState, RunSet, Title,
+};
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+    utils::hashbrown::HashMap,
+};
 use log::info;
-use ron::{de::from_reader, ser::to_writer};
-use serde::{Deserialize, Serialize};
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
-    fs::File,
-    io::{self, Result},
+    env,
+    fs::{self, File},
+    io::{self, Result, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 pub struct SavePlugin;
@@ -15,45 +33,847 @@ impl Plugin for SavePlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app.insert_resource(SavedArea(Area::forest()))
+        // A DIRECTORY INSERTED BEFORE THE PLUGIN WINS, SO TESTS CAN POINT IT ANYWHERE
+        if !app.world().contains_resource::<UserDirectory>() {
+            app.insert_resource(UserDirectory::resolve());
+        }
+        app.init_resource::<SaveGame>()
+            .init_resource::<SaveSlot>()
+            .init_resource::<SaveTask>()
             .add_event::<Save>()
             .add_systems(OnEnter(AppState::Exit), evw_save)
-            .add_systems(Update, evr_save);
+            .add_systems(
+                OnEnter(AppState::Playing),
+                restore_run
+                    .in_set(RunSet::Restore)
+                    .run_if(resource_exists::<PendingRestore>),
+            )
+            .add_systems(
+                OnExit(AppState::Playing),
+                (snapshot_run, flush_save).chain(),
+            )
+            .add_systems(OnEnter(AppState::GameOver), discard_run)
+            .add_systems(Update, quicksave.run_if(in_state(AppState::Playing)))
+            .add_systems(
+                Update,
+                (snapshot_run.run_if(in_state(AppState::Playing)), evr_save)
+                    .chain()
+                    .run_if(on_event::<Save>),
+            )
+            .add_systems(Update, poll_save_task.after(evr_save));
     }
 }
 
+pub const SAVE_SLOTS: usize = 3;
+// overrides the platform user directory when set
+pub const USER_DIR_ENV: &str = "RPGPR_USER_DIR";
+pub const USER_DIR_NAME: &str = "rpgpr";
+
 pub trait Saveable {
-    fn save(&self, filename: &str) -> Result<()>;
-    fn load(filename: &str) -> Result<Self>
+    fn save(&self, path: &Path) -> Result<()>;
+    fn load(path: &Path) -> Result<Self>
     where
         Self: Sized;
 }
+// ANY TYPE WITH AN impl Versioned SAVES THROUGH THE SAME ENVELOPE, ATOMIC WRITE AND BACKUPS
+// AN EMPTY impl IS ENOUGH FOR ANY Serialize + DeserializeOwned TYPE THAT HAS NEVER CHANGED SHAPE
+impl<T: Versioned> Saveable for T {
+    fn save(&self, path: &Path) -> Result<()> {
+        write_versioned(path, self)
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        read_versioned(path)
+    }
+}
+
+// WIRES A RESOURCE UP TO ITS OWN FILE IN THE USER DIRECTORY: LOADED IN PRESTARTUP
+// (DEFAULTS WRITTEN OUT ON FIRST RUN SO THERE'S A FILE TO EDIT)
+// save_on_event THEN WRITES IT WHENEVER AN E IS SENT, E.G. Save FOR RUN DATA,
+// A DEDICATED EVENT FOR ANYTHING THAT SHOULDN'T FOLLOW AUTOSAVES
+pub trait SaveableAppExt {
+    fn register_saveable<T: Resource + Versioned + Default>(
+        &mut self,
+        filename: &'static str,
+    ) -> &mut Self;
+    fn save_on_event<T: Resource + Versioned, E: Event>(&mut self) -> &mut Self;
+}
+impl SaveableAppExt for App {
+    fn register_saveable<T: Resource + Versioned + Default>(
+        &mut self,
+        filename: &'static str,
+    ) -> &mut Self {
+        self.insert_resource(SaveFile::<T>::new(filename))
+            .init_resource::<T>()
+            .add_systems(PreStartup, load_saveable::<T>)
+    }
+
+    // ONLY AFTER register_saveable::<T>, THE WRITE NEEDS ITS SaveFile
+    fn save_on_event<T: Resource + Versioned, E: Event>(&mut self) -> &mut Self {
+        self.add_event::<E>()
+            .add_systems(Update, save_saveable::<T, E>.run_if(on_event::<E>))
+    }
+}
+
+// the file a registered resource is kept in, relative to the user directory
+#[derive(Resource)]
+pub struct SaveFile<T> {
+    pub filename: &'static str,
+    _marker: PhantomData<T>,
+}
+impl<T> SaveFile<T> {
+    fn new(filename: &'static str) -> Self {
+        Self {
+            filename,
+            _marker: PhantomData,
+        }
+    }
+
+    // settings.ron >> SETTINGS, FOR NOTIFICATIONS
+    pub fn label(&self) -> String {
+        Path::new(self.filename)
+            .file_stem()
+            .map_or(self.filename.into(), |stem| stem.to_string_lossy())
+            .to_uppercase()
+    }
+}
+
+fn load_saveable<T: Resource + Versioned + Default>(
+    mut commands: Commands,
+    save_file: Res<SaveFile<T>>,
+    user_dir: Res<UserDirectory>,
+    mut evw_notify: EventWriter<Notify>,
+) {
+    let path = user_dir.path(save_file.filename);
+    let value = match T::load(&path) {
+        Ok(value) => {
+            info!("[INITIALIZED] {}", save_file.filename);
+            value
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let value = T::default();
+            if let Err(e) = value.save(&path) {
+                error!("[ERROR] Could not save {}. \n{}", path.display(), e);
+            }
+            info!("[INITIALIZED] {} (defaults)", save_file.filename);
+            value
+        }
+        Err(e) => {
+            error!("[ERROR] Could not load {}. \n{}", path.display(), e);
+            evw_notify.send(Notify::error(format!("{} RESET: {}", save_file.label(), e)));
+            T::default()
+        }
+    };
+    commands.insert_resource(value);
+}
+
+fn save_saveable<T: Resource + Versioned, E: Event>(
+    mut evr_save: EventReader<E>,
+    save_file: Res<SaveFile<T>>,
+    value: Res<T>,
+    user_dir: Res<UserDirectory>,
+    mut evw_notify: EventWriter<Notify>,
+) {
+    // SEVERAL SAVES IN ONE FRAME ONLY NEED ONE WRITE
+    evr_save.clear();
+    let path = user_dir.path(save_file.filename);
+    match value.save(&path) {
+        Ok(_) => {
+            info!("[SAVED] {}", save_file.filename);
+            evw_notify.send(Notify::info(format!("{} SAVED", save_file.label())));
+        }
+        Err(e) => {
+            error!("[ERROR] Could not save {}. \n{}", path.display(), e);
+            evw_notify.send(Notify::error(format!(
+                "{} NOT SAVED: {}",
+                save_file.label(),
+                e
+            )));
+        }
+    }
+}
+
+// EVERY SAVEABLE FILE IS WRITTEN AS (version: N, data: ...) SO OLD FILES CAN BE UPGRADED
+pub trait Versioned: Serialize + DeserializeOwned {
+    // bump whenever the serialized shape changes and add the step to migrate
+    const VERSION: u32 = 0;
+    // previous copies kept as <file>.bak.1 (newest) to <file>.bak.N
+    const BACKUPS: usize = 0;
+
+    // VERSION ASSUMED FOR FILES WRITTEN BEFORE THE ENVELOPE EXISTED
+    fn legacy_version(_text: &str) -> u32 {
+        0
+    }
+
+    // UPGRADES A FILE WRITTEN AT AN OLDER VERSION, ONE VERSION AT A TIME
+    // UNTIL A TYPE NEEDS ONE THE OLD FILE IS READ AS IS
+    fn migrate(_version: u32, text: &str) -> Result<Self> {
+        read_payload(text)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    version: u32,
+    data: T,
+}
+
+// only the version is read, the rest of the file is ignored
+#[derive(Deserialize)]
+struct VersionHeader {
+    version: u32,
+}
+
+// WRITES TO A TEMP FILE, CHECKS IT READS BACK, THEN RENAMES IT OVER THE OLD FILE
+// SO A CRASH MID-WRITE LEAVES THE PREVIOUS SAVE UNTOUCHED
+pub fn write_versioned<T: Versioned>(path: &Path, value: &T) -> Result<()> {
+    let envelope = Envelope {
+        version: T::VERSION,
+        data: value,
+    };
+    let text = to_string_pretty(&envelope, PrettyConfig::default()).map_err(io::Error::other)?;
+
+    // CREATES THE USER DIRECTORY ON FIRST WRITE
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = with_suffix(path, "tmp");
+    let mut file = File::create(&temp_path)?;
+    file.write_all(text.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    // ROUND TRIP: SAME BYTES ON DISK AND THEY STILL DESERIALIZE
+    let written = fs::read_to_string(&temp_path)?;
+    if written != text {
+        return Err(io::Error::other("written file does not match"));
+    }
+    ron::from_str::<Envelope<T>>(&written).map_err(io::Error::other)?;
+
+    rotate_backups(path, T::BACKUPS)?;
+    fs::rename(&temp_path, path)
+}
+
+// SHIFTS .bak.1..N UP BY ONE AND COPIES THE CURRENT FILE INTO .bak.1
+fn rotate_backups(path: &Path, backups: usize) -> Result<()> {
+    if backups == 0 || !path.exists() {
+        return Ok(());
+    }
+    for i in (1..backups).rev() {
+        let from = backup_path(path, i);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, i + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+pub fn backup_path(path: &Path, index: usize) -> PathBuf {
+    with_suffix(path, &format!("bak.{}", index))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+// FALLS BACK TO THE NEWEST BACKUP THAT STILL LOADS WHEN THE FILE ITSELF IS DAMAGED
+pub fn read_versioned<T: Versioned>(path: &Path) -> Result<T> {
+    let e = match read_file::<T>(path) {
+        Ok(value) => return Ok(value),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(e),
+        Err(e) => e,
+    };
+    for i in 1..=T::BACKUPS {
+        let backup = backup_path(path, i);
+        if let Ok(value) = read_file::<T>(&backup) {
+            warn!(
+                "[RECOVERED] {} from {}. \n{}",
+                path.display(),
+                backup.display(),
+                e
+            );
+            return Ok(value);
+        }
+    }
+    Err(e)
+}
+
+fn read_file<T: Versioned>(path: &Path) -> Result<T> {
+    let text = fs::read_to_string(path)?;
+    let version = match ron::from_str::<VersionHeader>(&text) {
+        Ok(header) => header.version,
+        Err(_) => T::legacy_version(&text),
+    };
+    // A TYPE STILL AT VERSION 0 MAY HAVE FILES FROM BEFORE THE ENVELOPE
+    if version == T::VERSION {
+        return read_payload(&text);
+    }
+    if version > T::VERSION {
+        return Err(io::Error::other(format!(
+            "version {} is newer than supported version {}",
+            version,
+            T::VERSION
+        )));
+    }
+    info!(
+        "[MIGRATED] {}: v{} >> v{}",
+        path.display(),
+        version,
+        T::VERSION
+    );
+    T::migrate(version, &text)
+}
+
+// OLDER VERSIONS MAY OR MAY NOT SIT INSIDE AN ENVELOPE
+pub fn read_payload<T: DeserializeOwned>(text: &str) -> Result<T> {
+    ron::from_str::<Envelope<T>>(text)
+        .map(|envelope| envelope.data)
+        .or_else(|_| ron::from_str::<T>(text))
+        .map_err(io::Error::other)
+}
+
+// writable per-user data (settings, saves), read-only game data lives in assets/data
+#[derive(Resource, Clone, Debug)]
+pub struct UserDirectory(pub PathBuf);
+impl UserDirectory {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self(path.into())
+    }
+
+    // ENV OVERRIDE FIRST, THEN THE PLATFORM'S DATA DIRECTORY, THEN THE WORKING DIRECTORY
+    pub fn resolve() -> Self {
+        let path = match env::var_os(USER_DIR_ENV) {
+            Some(path) => PathBuf::from(path),
+            None => platform_data_dir()
+                .map(|dir| dir.join(USER_DIR_NAME))
+                .unwrap_or_else(|| PathBuf::from(USER_DIR_NAME)),
+        };
+        info!("[INITIALIZED] User Directory: {}", path.display());
+        Self(path)
+    }
+
+    // EVERY USER FILE IS RESOLVED HERE, SO SAVE AND LOAD ALWAYS AGREE ON THE PATH
+    pub fn path(&self, filename: &str) -> PathBuf {
+        self.0.join(filename)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("APPDATA").map(PathBuf::from)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
 
 #[derive(Event)]
 pub struct Save;
 
-#[derive(Resource, Default, Deserialize, Serialize)]
-pub struct SavedArea(pub Area);
-impl Saveable for SavedArea {
-    fn save(&self, filename: &str) -> Result<()> {
-        let path = format!("{}/ron/{}", env!("CARGO_MANIFEST_DIR"), filename);
-        let file = File::create(path)?;
-        to_writer(file, self).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+// inserted when a slot is loaded, consumed when the run starts
+#[derive(Resource)]
+pub struct PendingRestore;
+
+// the slot the current run saves into, chosen from the main menu or the slot picker
+#[derive(Resource, Clone, Copy, Default, PartialEq, Debug)]
+pub struct SaveSlot(pub usize);
+impl SaveSlot {
+    pub fn save_file(&self) -> String {
+        format!("save_slot_{}.ron", self.0)
     }
 
-    fn load(filename: &str) -> Result<Self>
-    where
-        Self: Sized,
-    {
-        let file = File::open(filename)?;
-        from_reader(file).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    // SMALL SIDECAR SO THE SLOT PICKER NEVER DESERIALIZES A WHOLE RUN
+    pub fn meta_file(&self) -> String {
+        format!("save_slot_{}.meta.ron", self.0)
+    }
+
+    pub fn all() -> impl Iterator<Item = SaveSlot> {
+        (0..SAVE_SLOTS).map(SaveSlot)
+    }
+
+    // READS EVERY SLOT'S HEADER, FALLING BACK TO THE FULL SAVE WHEN THE HEADER IS MISSING OR OUTDATED
+    pub fn read_all(user_dir: &UserDirectory) -> Vec<Option<SaveMeta>> {
+        Self::all()
+            .map(|slot| {
+                SaveMeta::load(&user_dir.path(&slot.meta_file()))
+                    .or_else(|_| {
+                        SaveGame::load(&user_dir.path(&slot.save_file()))
+                            .map(|save_game| SaveMeta::from_save(&save_game))
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+
+    pub fn first_empty(user_dir: &UserDirectory) -> Option<SaveSlot> {
+        Self::all().find(|slot| !SaveGame::exists(user_dir, *slot))
+    }
+
+    pub fn most_recent(user_dir: &UserDirectory) -> Option<SaveSlot> {
+        Self::read_all(user_dir)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(slot, meta)| meta.map(|meta| (SaveSlot(slot), meta.timestamp)))
+            .max_by_key(|(_, timestamp)| *timestamp)
+            .map(|(slot, _)| slot)
+    }
+}
+
+// EVERYTHING NEEDED TO PUT A RUN BACK TOGETHER
+#[derive(Resource, Clone, Default, Deserialize, Serialize)]
+pub struct SaveGame {
+    pub party: Vec<SavedCharacter>,
+    pub loot: i32,
+    pub kills: u32,
+    pub distance: f32,
+    pub area: Area,
+    pub seed: u64,
+    pub rng_positions: HashMap<ChanceKind, u64>, // GameRng::positions
+    pub inventory: Inventory,
+    pub play_time: f32, // seconds
+}
+impl SaveGame {
+    pub fn exists(user_dir: &UserDirectory, slot: SaveSlot) -> bool {
+        user_dir.path(&slot.save_file()).exists()
+    }
+
+    pub fn delete(user_dir: &UserDirectory, slot: SaveSlot) -> Result<()> {
+        let save_path = user_dir.path(&slot.save_file());
+        let backups = (1..=Self::BACKUPS).map(|i| backup_path(&save_path, i));
+        let meta_path = user_dir.path(&slot.meta_file());
+        for path in backups.chain([save_path.clone(), meta_path]) {
+            match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    // LOADS A SLOT AND HANDS IT TO restore_run FOR THE NEXT ONENTER(PLAYING)
+    pub fn queue_restore(
+        commands: &mut Commands,
+        user_dir: &UserDirectory,
+        slot: SaveSlot,
+    ) -> Result<()> {
+        let save_game = SaveGame::load(&user_dir.path(&slot.save_file()))?;
+        commands.insert_resource(save_game);
+        commands.insert_resource(slot);
+        commands.insert_resource(PendingRestore);
+        Ok(())
+    }
+}
+impl Versioned for SaveGame {
+    const VERSION: u32 = 3;
+    const BACKUPS: usize = 3;
+
+    fn legacy_version(text: &str) -> u32 {
+        match ron::from_str::<SaveGameV0>(text) {
+            Ok(_) => 0,
+            Err(_) => 1,
+        }
+    }
+
+    fn migrate(version: u32, text: &str) -> Result<Self> {
+        match version {
+            0 => {
+                let v1 = SaveGameV1::from(read_payload::<SaveGameV0>(text)?);
+                Ok(SaveGameV2::from(v1).into())
+            }
+            1 => Ok(SaveGameV2::from(read_payload::<SaveGameV1>(text)?).into()),
+            2 => Ok(read_payload::<SaveGameV2>(text)?.into()),
+            _ => Err(io::Error::other(format!(
+                "no migration from version {}",
+                version
+            ))),
+        }
+    }
+}
+
+// v0: the original saved_area.ron, only the area with list based weightings
+#[derive(Deserialize)]
+struct SaveGameV0(AreaV0);
+
+#[derive(Deserialize)]
+struct AreaV0 {
+    title: String,
+    kind: AreaKindV0,
+    weighting_bg: WeightingV0,
+    weighting_loot: WeightingV0,
+    weighting_enemy: WeightingV0,
+}
+
+#[derive(Deserialize)]
+enum AreaKindV0 {
+    #[serde(alias = "DEFAULT")]
+    Default,
+    Forest,
+    Desert,
+    Swamp,
+}
+
+#[derive(Deserialize)]
+struct WeightingV0 {
+    kind: ChanceKind,
+    chances: Vec<Chance>,
+}
+impl From<WeightingV0> for Weighting {
+    // THE POOL DIDN'T EXIST YET, ONLY THE FIRST CHANCE CARRIES OVER
+    fn from(v0: WeightingV0) -> Self {
+        let chance = v0.chances.first().copied().unwrap_or_default();
+        Weighting::new(chance.success, chance.failure, v0.kind, HashMap::new())
+    }
+}
+impl From<SaveGameV0> for SaveGameV1 {
+    fn from(v0: SaveGameV0) -> Self {
+        let SaveGameV0(area) = v0;
+        Self {
+            party: Vec::new(),
+            loot: 0,
+            kills: 0,
+            distance: 0.,
+            area: Area {
+                title: Title(area.title),
+                kind: match area.kind {
+                    AreaKindV0::Default => AreaKind::Default,
+                    AreaKindV0::Forest => AreaKind::Forest,
+                    AreaKindV0::Desert => AreaKind::Desert,
+                    AreaKindV0::Swamp => AreaKind::Swamp,
+                },
+                weighting_bg: area.weighting_bg.into(),
+                weighting_loot: area.weighting_loot.into(),
+                weighting_enemy: area.weighting_enemy.into(),
+            },
+            seed: 0,
+            inventory: Inventory::default(),
+        }
+    }
+}
+
+// v1: the first full run snapshot, before play time was tracked
+#[derive(Deserialize)]
+struct SaveGameV1 {
+    party: Vec<SavedCharacter>,
+    loot: i32,
+    kills: u32,
+    distance: f32,
+    area: Area,
+    seed: u64,
+    inventory: Inventory,
+}
+impl From<SaveGameV1> for SaveGameV2 {
+    fn from(v1: SaveGameV1) -> Self {
+        Self {
+            party: v1.party,
+            loot: v1.loot,
+            kills: v1.kills,
+            distance: v1.distance,
+            area: v1.area,
+            seed: v1.seed,
+            inventory: v1.inventory,
+            play_time: 0.,
+        }
     }
 }
 
-fn evr_save(mut evr_save: EventReader<Save>, area: ResMut<SavedArea>) {
-    for _ev in evr_save.read() {
-        info!("[EVENT] [READ] Save Game");
-        let _ = area.save("saved_area.ron");
+// v2: before the rng streams' positions were stored
+#[derive(Deserialize)]
+struct SaveGameV2 {
+    party: Vec<SavedCharacter>,
+    loot: i32,
+    kills: u32,
+    distance: f32,
+    area: Area,
+    seed: u64,
+    inventory: Inventory,
+    play_time: f32,
+}
+impl From<SaveGameV2> for SaveGame {
+    // EVERY STREAM STARTS OVER FROM THE SEED
+    fn from(v2: SaveGameV2) -> Self {
+        Self {
+            party: v2.party,
+            loot: v2.loot,
+            kills: v2.kills,
+            distance: v2.distance,
+            area: v2.area,
+            seed: v2.seed,
+            rng_positions: HashMap::new(),
+            inventory: v2.inventory,
+            play_time: v2.play_time,
+        }
+    }
+}
+
+// header written next to each save, what the slot picker shows
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct SaveMeta {
+    pub party: Vec<String>,
+    pub area: String,
+    pub distance: f32,
+    pub play_time: f32, // seconds
+    pub timestamp: u64, // unix seconds
+}
+impl SaveMeta {
+    pub fn from_save(save_game: &SaveGame) -> Self {
+        Self {
+            party: save_game
+                .party
+                .iter()
+                .map(|saved| saved.character.title.0.clone())
+                .collect(),
+            area: save_game.area.title.0.clone(),
+            distance: save_game.distance,
+            play_time: save_game.play_time,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+// OLDER HEADERS AREN'T MIGRATED, read_all REBUILDS THEM FROM THE SAVE INSTEAD
+impl Versioned for SaveMeta {
+    const VERSION: u32 = 1;
+}
+
+// at most one run save is written at a time, a newer snapshot waits in `queued`
+#[derive(Resource, Default)]
+pub struct SaveTask {
+    task: Option<(SaveSlot, Task<Result<()>>)>,
+    queued: Option<(SaveGame, SaveSlot, UserDirectory)>,
+}
+impl SaveTask {
+    pub fn is_busy(&self) -> bool {
+        self.task.is_some() || self.queued.is_some()
+    }
+
+    // ONLY THE NEWEST SNAPSHOT MATTERS, AN OLDER QUEUED ONE IS REPLACED
+    pub fn queue(&mut self, save_game: SaveGame, slot: SaveSlot, user_dir: UserDirectory) {
+        self.queued = Some((save_game, slot, user_dir));
+        if self.task.is_none() {
+            self.start_queued();
+        }
+    }
+
+    fn start_queued(&mut self) {
+        let Some((save_game, slot, user_dir)) = self.queued.take() else {
+            return;
+        };
+        let task = IoTaskPool::get().spawn(async move {
+            // HEADER LAST SO IT NEVER DESCRIBES A SAVE THAT FAILED TO WRITE
+            save_game
+                .save(&user_dir.path(&slot.save_file()))
+                .and_then(|_| {
+                    SaveMeta::from_save(&save_game).save(&user_dir.path(&slot.meta_file()))
+                })
+        });
+        self.task = Some((slot, task));
+    }
+
+    pub fn poll(&mut self) -> Option<(SaveSlot, Result<()>)> {
+        let (slot, task) = self.task.as_mut()?;
+        let slot = *slot;
+        let result = block_on(future::poll_once(task))?;
+        self.task = None;
+        self.start_queued();
+        Some((slot, result))
+    }
+
+    // WAITS FOR THE WRITE IN FLIGHT AND THEN ANYTHING QUEUED BEHIND IT
+    pub fn flush(&mut self) -> Vec<(SaveSlot, Result<()>)> {
+        let mut results = Vec::new();
+        while let Some((slot, task)) = self.task.take() {
+            results.push((slot, block_on(task)));
+            self.start_queued();
+        }
+        results
+    }
+
+    // WAITS FOR THE WRITE IN FLIGHT AND DROPS ANYTHING QUEUED
+    pub fn finish(&mut self) {
+        self.queued = None;
+        if let Some((_, task)) = self.task.take() {
+            if let Err(e) = block_on(task) {
+                error!("[ERROR] Could not save. \n{}", e);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct SavedCharacter {
+    pub character: CharacterBundle,
+    pub health: Health,
+    pub equipment: Equipment,
+}
+
+// COPIES THE LIVE RUN INTO THE SAVEGAME RESOURCE
+pub fn snapshot_run(
+    mut save_game: ResMut<SaveGame>,
+    query_party: Query<(CharacterQuery, &Lane, &Health, &Equipment), With<Player>>,
+    player_loot: Res<PlayerLoot>,
+    player_kills: Res<PlayerKills>,
+    player_distance: Res<PlayerDistance>,
+    player_time: Res<PlayerTime>,
+    current_area: Res<CurrentArea>,
+    rng: Res<GameRng>,
+    inventory: Res<Inventory>,
+) {
+    // RESTORED BY INDEX, SO THE PARTY IS STORED IN LANE ORDER
+    let mut party = query_party.iter().collect::<Vec<_>>();
+    party.sort_by_key(|(_, lane, _, _)| **lane);
+    *save_game = SaveGame {
+        party: party
+            .into_iter()
+            .map(|(character, _, health, equipment)| SavedCharacter {
+                character: character.bundle(),
+                health: *health,
+                equipment: *equipment,
+            })
+            .collect(),
+        loot: **player_loot,
+        kills: **player_kills,
+        distance: **player_distance,
+        area: current_area.0.clone(),
+        seed: rng.seed(),
+        rng_positions: rng.positions(),
+        inventory: inventory.clone(),
+        play_time: **player_time,
+    };
+    info!("[MODIFIED] SaveGame Snapshot");
+}
+
+// RUNS AFTER THE FRESH RUN DEFAULTS SO THE SAVE WINS
+fn restore_run(
+    mut commands: Commands,
+    mut evw_spawn_player: EventWriter<SpawnPlayer>,
+    save_game: Res<SaveGame>,
+    mut player_loot: ResMut<PlayerLoot>,
+    mut player_kills: ResMut<PlayerKills>,
+    mut player_distance: ResMut<PlayerDistance>,
+    mut player_time: ResMut<PlayerTime>,
+    mut current_area: ResMut<CurrentArea>,
+    mut rng: ResMut<GameRng>,
+    mut inventory: ResMut<Inventory>,
+) {
+    for (lane, saved) in save_game.party.iter().enumerate() {
+        evw_spawn_player.send(SpawnPlayer {
+            lane,
+            saved: Some(saved.clone()),
+        });
+    }
+    // SAVES FROM BEFORE THE PARTY WAS STORED START WITH A FRESH CHARACTER
+    if save_game.party.is_empty() {
+        evw_spawn_player.send(SpawnPlayer {
+            lane: 0,
+            saved: None,
+        });
+    }
+    **player_loot = save_game.loot;
+    **player_kills = save_game.kills;
+    **player_distance = save_game.distance;
+    **player_time = save_game.play_time;
+    current_area.0 = save_game.area.clone();
+    rng.resume(save_game.seed, &save_game.rng_positions);
+    *inventory = save_game.inventory.clone();
+    commands.remove_resource::<PendingRestore>();
+    info!("[RESTORED] SaveGame");
+}
+
+// A FALLEN PARTY CAN'T BE CONTINUED
+fn discard_run(
+    mut save_game: ResMut<SaveGame>,
+    user_dir: Res<UserDirectory>,
+    save_slot: Res<SaveSlot>,
+    mut save_task: ResMut<SaveTask>,
+) {
+    *save_game = SaveGame::default();
+    // A WRITE STILL IN FLIGHT WOULD BRING THE RUN BACK AFTER THE DELETE
+    save_task.finish();
+    if let Err(e) = SaveGame::delete(&user_dir, *save_slot) {
+        error!(
+            "[ERROR] Could not delete {}. \n{}",
+            save_slot.save_file(),
+            e
+        );
+    }
+    info!("[DELETED] SaveGame: Slot {}", save_slot.0);
+}
+
+fn quicksave(keys: Res<ButtonInput<KeyCode>>, mut evw_save: EventWriter<Save>) {
+    if keys.just_pressed(KeyCode::F5) {
+        evw_save.send(Save);
+        info!("[EVENT] [WRITE] Save Game.");
+    }
+}
+
+// SNAPSHOTS ARE WRITTEN ON THE IO TASK POOL SO THE SCROLL LOOP NEVER WAITS ON THE DISK
+fn evr_save(
+    mut evr_save: EventReader<Save>,
+    save_game: Res<SaveGame>,
+    user_dir: Res<UserDirectory>,
+    save_slot: Res<SaveSlot>,
+    mut save_task: ResMut<SaveTask>,
+) {
+    // SEVERAL SAVES IN ONE FRAME ONLY NEED ONE WRITE
+    if evr_save.read().count() == 0 {
+        return;
+    }
+    info!("[EVENT] [READ] Save Game");
+    // NOTHING TO SAVE OUTSIDE OF A RUN
+    if save_game.party.is_empty() {
+        return;
+    }
+    save_task.queue(save_game.clone(), *save_slot, user_dir.clone());
+}
+
+// REPORTS A FINISHED WRITE AND STARTS THE NEXT QUEUED ONE
+fn poll_save_task(mut save_task: ResMut<SaveTask>, mut evw_notify: EventWriter<Notify>) {
+    let Some((slot, result)) = save_task.poll() else {
+        return;
+    };
+    report_save(slot, result, &mut evw_notify);
+}
+
+// LEAVING A RUN WAITS ON ITS SAVE, SO THE MAIN MENU'S CONTINUE SEES WHAT WAS JUST WRITTEN
+fn flush_save(
+    mut save_events: ResMut<Events<Save>>,
+    save_game: Res<SaveGame>,
+    user_dir: Res<UserDirectory>,
+    save_slot: Res<SaveSlot>,
+    mut save_task: ResMut<SaveTask>,
+    mut evw_notify: EventWriter<Notify>,
+) {
+    // E.G. SAVE & QUIT, SENT THIS FRAME BUT NOT READ BY evr_save YET
+    if save_events.drain().count() > 0 && !save_game.party.is_empty() {
+        save_task.queue(save_game.clone(), *save_slot, user_dir.clone());
+    }
+    for (slot, result) in save_task.flush() {
+        report_save(slot, result, &mut evw_notify);
+    }
+}
+
+fn report_save(slot: SaveSlot, result: Result<()>, evw_notify: &mut EventWriter<Notify>) {
+    match result {
+        Ok(_) => info!("[SAVED] SaveGame: Slot {}", slot.0),
+        Err(e) => {
+            error!("[ERROR] Could not save {}. \n{}", slot.save_file(), e);
+            evw_notify.send(Notify::error(format!("SAVE FAILED: {}", e)));
+        }
     }
 }
 
@@ -61,3 +881,212 @@ fn evw_save(mut evw_save: EventWriter<Save>) {
     evw_save.send(Save);
     info!("[EVENT] [WRITE] Save Game.");
 }
+
+// checked in files as earlier versions wrote them, shared by every Versioned test
+#[cfg(test)]
+pub(crate) fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{item::ItemID, ID};
+
+    fn assert_run(save_game: &SaveGame) {
+        assert_eq!(save_game.party.len(), 1);
+        let saved = &save_game.party[0];
+        assert_eq!(saved.character.title.0, "Old Man");
+        assert_eq!((saved.health.current, saved.health.max), (2, 3));
+        assert_eq!(saved.equipment.main_hand.map(|wid| *wid.0), Some(1));
+        assert_eq!(save_game.loot, 17);
+        assert_eq!(save_game.kills, 3);
+        assert_eq!(save_game.distance, 1234.);
+        assert_eq!(save_game.area.title.0, "Frightening Forest");
+        assert!(save_game.area.kind == AreaKind::Forest);
+        assert_eq!(save_game.area.weighting_enemy.pool().len(), 2);
+        assert_eq!(save_game.seed, 42);
+        assert_eq!(save_game.inventory.count(ItemID(ID(2))), 3);
+        assert_eq!(save_game.inventory.capacity, 10);
+        // OLDER SAVES RESUME EVERY STREAM FROM THE SEED
+        assert!(save_game.rng_positions.is_empty());
+    }
+
+    // A FRESH DIRECTORY PER TEST, REMOVED AGAIN ON DROP
+    struct TempDir(PathBuf);
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = env::temp_dir().join(format!("rpgpr-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn run(distance: f32) -> SaveGame {
+        SaveGame {
+            distance,
+            ..default()
+        }
+    }
+
+    // SERIALIZES FINE BUT NEVER READS BACK, SO THE ROUND TRIP CHECK FAILS
+    #[derive(Serialize, Deserialize)]
+    struct Unreadable(#[serde(deserialize_with = "reject")] u32);
+    impl Versioned for Unreadable {
+        const BACKUPS: usize = 2;
+    }
+    fn reject<'de, D: serde::Deserializer<'de>>(_: D) -> std::result::Result<u32, D::Error> {
+        Err(serde::de::Error::custom("unreadable"))
+    }
+
+    // NOTHING BUT THE EMPTY impl
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Plain {
+        count: u32,
+    }
+    impl Versioned for Plain {}
+
+    #[test]
+    fn plain_type_round_trips_and_reads_files_without_envelope() {
+        let dir = TempDir::new("plain");
+        let path = dir.0.join("plain.ron");
+        write_versioned(&path, &Plain { count: 4 }).unwrap();
+        assert_eq!(read_versioned::<Plain>(&path).unwrap(), Plain { count: 4 });
+        fs::write(&path, "(count: 7)").unwrap();
+        assert_eq!(read_versioned::<Plain>(&path).unwrap(), Plain { count: 7 });
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_to_newest_backup() {
+        let dir = TempDir::new("fallback");
+        let path = dir.0.join("save.ron");
+        write_versioned(&path, &run(1.)).unwrap();
+        write_versioned(&path, &run(2.)).unwrap();
+        fs::write(&path, "not ron at all").unwrap();
+        let save_game = read_versioned::<SaveGame>(&path).unwrap();
+        assert_eq!(save_game.distance, 1.);
+    }
+
+    #[test]
+    fn missing_file_does_not_fall_back() {
+        let dir = TempDir::new("missing");
+        let path = dir.0.join("save.ron");
+        write_versioned(&path, &run(1.)).unwrap();
+        write_versioned(&path, &run(2.)).unwrap();
+        fs::remove_file(&path).unwrap();
+        let e = read_versioned::<SaveGame>(&path).err().unwrap();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn rotation_keeps_at_most_backups() {
+        let dir = TempDir::new("rotation");
+        let path = dir.0.join("save.ron");
+        for distance in 1..=SaveGame::BACKUPS + 3 {
+            write_versioned(&path, &run(distance as f32)).unwrap();
+        }
+        let newest = SaveGame::BACKUPS + 3;
+        assert_eq!(
+            read_file::<SaveGame>(&path).unwrap().distance,
+            newest as f32
+        );
+        // .bak.1 IS THE WRITE BEFORE THE CURRENT ONE, EACH OLDER BACKUP ONE FURTHER BACK
+        for i in 1..=SaveGame::BACKUPS {
+            let backup = read_file::<SaveGame>(&backup_path(&path, i)).unwrap();
+            assert_eq!(backup.distance, (newest - i) as f32);
+        }
+        assert!(!backup_path(&path, SaveGame::BACKUPS + 1).exists());
+    }
+
+    #[test]
+    fn failed_round_trip_leaves_the_original() {
+        let dir = TempDir::new("round-trip");
+        let path = dir.0.join("unreadable.ron");
+        fs::write(&path, "original").unwrap();
+        assert!(write_versioned(&path, &Unreadable(1)).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+        assert!(!backup_path(&path, 1).exists());
+    }
+
+    #[test]
+    fn env_var_overrides_user_directory() {
+        let dir = TempDir::new("user-dir");
+        env::set_var(USER_DIR_ENV, &dir.0);
+        let user_dir = UserDirectory::resolve();
+        env::remove_var(USER_DIR_ENV);
+        assert_eq!(user_dir.0, dir.0);
+        let path = user_dir.path(&SaveSlot(0).save_file());
+        assert_eq!(path.parent(), Some(dir.0.as_path()));
+        write_versioned(&path, &run(3.)).unwrap();
+        assert_eq!(read_versioned::<SaveGame>(&path).unwrap().distance, 3.);
+    }
+
+    #[test]
+    fn flush_writes_everything_queued_before_returning() {
+        IoTaskPool::get_or_init(bevy::tasks::TaskPool::new);
+        let dir = TempDir::new("flush");
+        let user_dir = UserDirectory(dir.0.clone());
+        let mut save_task = SaveTask::default();
+        save_task.queue(run(1.), SaveSlot(0), user_dir.clone());
+        save_task.queue(run(2.), SaveSlot(1), user_dir.clone());
+        let results = save_task.flush();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+        assert!(!save_task.is_busy());
+        assert!(SaveGame::exists(&user_dir, SaveSlot(1)));
+        assert_eq!(SaveSlot::most_recent(&user_dir).map(|slot| slot.0), Some(1));
+    }
+
+    #[test]
+    fn save_game_v0_migrates() {
+        // VERBATIM FROM THE FIRST RELEASE, NO CHANCES WERE EVER WRITTEN
+        let save_game = read_versioned::<SaveGame>(&fixture("save_game_v0.ron")).unwrap();
+        assert_eq!(save_game.area.title.0, "Default Level");
+        assert!(save_game.area.kind == AreaKind::Default);
+        let enemy = &save_game.area.weighting_enemy;
+        assert_eq!(enemy.kind, ChanceKind::DEFAULT);
+        assert_eq!(enemy.chance.probability(), 0.);
+        assert!(enemy.pool().is_empty());
+        assert!(save_game.party.is_empty());
+        assert_eq!(save_game.seed, 0);
+        assert_eq!(save_game.play_time, 0.);
+    }
+
+    #[test]
+    fn save_game_v0_with_chances_migrates() {
+        let save_game = read_versioned::<SaveGame>(&fixture("save_game_v0_chances.ron")).unwrap();
+        assert_eq!(save_game.area.title.0, "Default Level");
+        assert!(save_game.area.kind == AreaKind::Default);
+        // ONLY THE FIRST CHANCE CARRIES OVER, THE POOL STARTS EMPTY
+        let enemy = &save_game.area.weighting_enemy;
+        assert_eq!(enemy.kind, ChanceKind::ENEMY);
+        assert_eq!((enemy.chance.success, enemy.chance.failure), (0.3, 0.7));
+        assert!(enemy.pool().is_empty());
+        assert_eq!(save_game.area.weighting_bg.chance.probability(), 0.);
+        assert!(save_game.party.is_empty());
+        assert_eq!(save_game.seed, 0);
+        assert_eq!(save_game.play_time, 0.);
+    }
+
+    #[test]
+    fn save_game_v1_migrates() {
+        let save_game = read_versioned::<SaveGame>(&fixture("save_game_v1.ron")).unwrap();
+        assert_run(&save_game);
+        assert_eq!(save_game.play_time, 0.);
+    }
+
+    #[test]
+    fn save_game_v2_migrates() {
+        let save_game = read_versioned::<SaveGame>(&fixture("save_game_v2.ron")).unwrap();
+        assert_run(&save_game);
+        assert_eq!(save_game.play_time, 754.5);
+    }
+}
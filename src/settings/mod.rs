@@ -1,7 +1,18 @@
-use bevy::prelude::*;
-use ron::de::from_reader;
+use crate::{
+    audio::{EffectsChannel, MusicChannel},
+    save::{read_payload, SaveableAppExt, Versioned},
+    theme::{ButtonWidth, ColorRole, FontRole, Theme, THEMES},
+    ui::{spawn_button, Focusable, MenuAction, MenuAppExt, Screens},
+    AppState, PauseState,
+};
+use bevy::{
+    prelude::*,
+    ui::RelativeCursorPosition,
+    window::{Monitor, PresentMode, WindowMode},
+};
+use bevy_kira_audio::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::fs::File;
+use std::io::{self, Result};
 
 pub struct SettingsPlugin;
 impl Plugin for SettingsPlugin {
@@ -11,33 +22,242 @@ impl Plugin for SettingsPlugin {
 
     fn build(&self, app: &mut App) {
         app.insert_resource(Resolutions::init())
-            .insert_resource(Settings::load());
+            .register_saveable::<Settings>(SETTINGS_FILE)
+            .save_on_event::<Settings, SaveSettings>()
+            .add_menu_action::<SettingsButton>()
+            .add_systems(Update, apply_settings.run_if(resource_changed::<Settings>))
+            // OPENED FROM THE MAIN MENU OR OVER A PAUSED RUN
+            .add_systems(OnEnter(AppState::Settings), spawn_settings_menu)
+            .add_systems(OnEnter(PauseState::Settings), spawn_settings_menu)
+            .add_systems(
+                Update,
+                (
+                    evr_settings_menu,
+                    volume_slider_system,
+                    update_settings_menu.run_if(resource_changed::<Settings>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Settings).or(in_state(PauseState::Settings))),
+            )
+            .add_systems(OnExit(AppState::Settings), despawn_settings_menu)
+            .add_systems(OnExit(PauseState::Settings), despawn_settings_menu);
     }
 }
 
-#[derive(Resource, Default, Serialize, Deserialize)]
+pub const SETTINGS_FILE: &str = "settings.ron";
+
+// writes settings.ron, kept apart from the run's Save so autosaves never persist a preview
+#[derive(Event)]
+pub struct SaveSettings;
+
+// STEP FOR THE VOLUME ARROWS, THE SLIDER ITSELF IS CONTINUOUS
+pub const VOLUME_STEP: f32 = 0.1;
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Settings {
     pub resolution: Vec2,
     pub monitor: usize,
+    pub seed: Option<u64>,      // fixed run seed, random when None
+    pub autosave_interval: f32, // seconds between autosaves, 0 disables the timer
+    pub fullscreen: bool,
+    pub resizable: bool, // dragging the window edges relayouts the scene
+    pub vsync: bool,
+    pub volume: Volume,
+    pub theme: String, // one of THEMES
 }
-impl Settings {
-    fn load() -> Self {
-        let input_path = format!("{}/ron/settings.ron", env!("CARGO_MANIFEST_DIR"));
-        let f = File::open(input_path.clone()).expect("Failed opening file");
-        let settings: Settings = match from_reader(f) {
-            Ok(x) => {
-                info!("[INITIALIZED] Settings");
-                x
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            resolution: Vec2::new(1920., 1080.),
+            monitor: 0,
+            seed: None,
+            autosave_interval: 60.,
+            fullscreen: false,
+            resizable: false,
+            vsync: true,
+            volume: Volume::default(),
+            theme: THEMES[0].to_string(),
+        }
+    }
+}
+impl Versioned for Settings {
+    const VERSION: u32 = 5;
+
+    fn migrate(version: u32, text: &str) -> Result<Self> {
+        match version {
+            0 => {
+                let v1 = SettingsV1::from(read_payload::<SettingsV0>(text)?);
+                Ok(SettingsV4::from(SettingsV3::from(SettingsV2::from(v1))).into())
             }
-            Err(e) => {
-                eprintln!("[ERROR] Could not deserialize {}. \n{}", input_path, e);
-                Self::default()
+            1 => {
+                let v2 = SettingsV2::from(read_payload::<SettingsV1>(text)?);
+                Ok(SettingsV4::from(SettingsV3::from(v2)).into())
             }
-        };
-        settings
+            2 => Ok(SettingsV4::from(SettingsV3::from(read_payload::<SettingsV2>(text)?)).into()),
+            3 => Ok(SettingsV4::from(read_payload::<SettingsV3>(text)?).into()),
+            4 => Ok(read_payload::<SettingsV4>(text)?.into()),
+            _ => Err(io::Error::other(format!(
+                "no migration from version {}",
+                version
+            ))),
+        }
+    }
+}
+// v0: the unversioned settings.ron, seed was added later so it may be missing
+#[derive(Deserialize)]
+struct SettingsV0 {
+    resolution: Vec2,
+    monitor: usize,
+    #[serde(default)]
+    seed: Option<u64>,
+}
+impl From<SettingsV0> for SettingsV1 {
+    fn from(v0: SettingsV0) -> Self {
+        Self {
+            resolution: v0.resolution,
+            monitor: v0.monitor,
+            seed: v0.seed,
+        }
+    }
+}
+
+// v1: before autosave
+#[derive(Deserialize)]
+struct SettingsV1 {
+    resolution: Vec2,
+    monitor: usize,
+    seed: Option<u64>,
+}
+impl From<SettingsV1> for SettingsV2 {
+    fn from(v1: SettingsV1) -> Self {
+        Self {
+            resolution: v1.resolution,
+            monitor: v1.monitor,
+            seed: v1.seed,
+            autosave_interval: Settings::default().autosave_interval,
+        }
     }
 }
 
+// v2: before window mode, vsync and volume
+#[derive(Deserialize)]
+struct SettingsV2 {
+    resolution: Vec2,
+    monitor: usize,
+    seed: Option<u64>,
+    autosave_interval: f32,
+}
+impl From<SettingsV2> for SettingsV3 {
+    fn from(v2: SettingsV2) -> Self {
+        let defaults = Settings::default();
+        Self {
+            resolution: v2.resolution,
+            monitor: v2.monitor,
+            seed: v2.seed,
+            autosave_interval: v2.autosave_interval,
+            fullscreen: defaults.fullscreen,
+            vsync: defaults.vsync,
+            volume: defaults.volume,
+        }
+    }
+}
+
+// v3: before the resizable window
+#[derive(Deserialize)]
+struct SettingsV3 {
+    resolution: Vec2,
+    monitor: usize,
+    seed: Option<u64>,
+    autosave_interval: f32,
+    fullscreen: bool,
+    vsync: bool,
+    volume: Volume,
+}
+impl From<SettingsV3> for SettingsV4 {
+    fn from(v3: SettingsV3) -> Self {
+        Self {
+            resolution: v3.resolution,
+            monitor: v3.monitor,
+            seed: v3.seed,
+            autosave_interval: v3.autosave_interval,
+            fullscreen: v3.fullscreen,
+            resizable: Settings::default().resizable,
+            vsync: v3.vsync,
+            volume: v3.volume,
+        }
+    }
+}
+
+// v4: before themes
+#[derive(Deserialize)]
+struct SettingsV4 {
+    resolution: Vec2,
+    monitor: usize,
+    seed: Option<u64>,
+    autosave_interval: f32,
+    fullscreen: bool,
+    resizable: bool,
+    vsync: bool,
+    volume: Volume,
+}
+impl From<SettingsV4> for Settings {
+    fn from(v4: SettingsV4) -> Self {
+        Self {
+            resolution: v4.resolution,
+            monitor: v4.monitor,
+            seed: v4.seed,
+            autosave_interval: v4.autosave_interval,
+            fullscreen: v4.fullscreen,
+            resizable: v4.resizable,
+            vsync: v4.vsync,
+            volume: v4.volume,
+            ..default()
+        }
+    }
+}
+
+// 0.0-1.0, master scales the music and effects channels
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Volume {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+}
+impl Default for Volume {
+    fn default() -> Self {
+        Self {
+            master: 0.8,
+            music: 0.8,
+            effects: 0.8,
+        }
+    }
+}
+impl Volume {
+    pub fn get(&self, kind: VolumeKind) -> f32 {
+        match kind {
+            VolumeKind::Master => self.master,
+            VolumeKind::Music => self.music,
+            VolumeKind::Effects => self.effects,
+        }
+    }
+
+    pub fn set(&mut self, kind: VolumeKind, value: f32) {
+        let value = value.clamp(0., 1.);
+        match kind {
+            VolumeKind::Master => self.master = value,
+            VolumeKind::Music => self.music = value,
+            VolumeKind::Effects => self.effects = value,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum VolumeKind {
+    Master,
+    Music,
+    Effects,
+}
+
 #[derive(Resource, Default, Serialize, Deserialize)]
 pub struct Resolutions {
     sd: Vec2,  // 480p
@@ -52,4 +272,456 @@ impl Resolutions {
             uhd: Vec2::new(3840., 2160.),
         }
     }
+
+    pub fn all(&self) -> [(&'static str, Vec2); 3] {
+        [("SD", self.sd), ("HD", self.hd), ("UHD", self.uhd)]
+    }
+
+    // STEPS THROUGH THE PRESETS, A CUSTOM RESOLUTION STARTS FROM THE FIRST ONE
+    pub fn step(&self, current: Vec2, forward: bool) -> Vec2 {
+        let all = self.all();
+        let next = match all
+            .iter()
+            .position(|(_, resolution)| *resolution == current)
+        {
+            Some(i) if forward => (i + 1) % all.len(),
+            Some(i) => (i + all.len() - 1) % all.len(),
+            None => 0,
+        };
+        all[next].1
+    }
+
+    pub fn label(&self, resolution: Vec2) -> String {
+        let name = self
+            .all()
+            .iter()
+            .find(|(_, preset)| *preset == resolution)
+            .map(|(name, _)| *name)
+            .unwrap_or("CUSTOM");
+        format!("{} {}x{}", name, resolution.x, resolution.y)
+    }
+}
+
+// APPLIES ONLY WHAT CHANGED SO E.G. A VOLUME TWEAK DOESN'T RECENTER THE WINDOW
+fn apply_settings(
+    settings: Res<Settings>,
+    mut applied: Local<Option<Settings>>,
+    mut query_window: Query<&mut Window>,
+    audio: Res<Audio>,
+    music: Res<AudioChannel<MusicChannel>>,
+    effects: Res<AudioChannel<EffectsChannel>>,
+) {
+    let Ok(mut window) = query_window.get_single_mut() else {
+        return;
+    };
+    let previous = applied.as_ref();
+    // CHANNELS DON'T NEST, SO MASTER IS MULTIPLIED INTO EACH ONE
+    if previous.map(|previous| previous.volume) != Some(settings.volume) {
+        let volume = settings.volume;
+        audio.set_volume(volume.master as f64);
+        music.set_volume((volume.master * volume.music) as f64);
+        effects.set_volume((volume.master * volume.effects) as f64);
+        info!(
+            "[MODIFIED] Volume : master {:.2}, music {:.2}, effects {:.2}",
+            volume.master, volume.music, volume.effects
+        );
+    }
+    if previous.map(|previous| previous.resolution) != Some(settings.resolution) {
+        window
+            .resolution
+            .set(settings.resolution.x, settings.resolution.y);
+        info!(
+            "[MODIFIED] Window Resolution : ({},{})",
+            settings.resolution.x, settings.resolution.y
+        );
+    }
+    let monitor = MonitorSelection::Index(settings.monitor);
+    if previous.map(|previous| (previous.monitor, previous.fullscreen))
+        != Some((settings.monitor, settings.fullscreen))
+    {
+        window.position.center(monitor);
+        window.mode = match settings.fullscreen {
+            true => WindowMode::BorderlessFullscreen(monitor),
+            false => WindowMode::Windowed,
+        };
+        info!(
+            "[MODIFIED] Window Mode : {:?} on monitor {}",
+            window.mode, settings.monitor
+        );
+    }
+    if previous.map(|previous| previous.resizable) != Some(settings.resizable) {
+        window.resizable = settings.resizable;
+        info!("[MODIFIED] Window Resizable : {}", settings.resizable);
+    }
+    if previous.map(|previous| previous.vsync) != Some(settings.vsync) {
+        window.present_mode = match settings.vsync {
+            true => PresentMode::AutoVsync,
+            false => PresentMode::AutoNoVsync,
+        };
+        info!("[MODIFIED] Window VSync : {}", settings.vsync);
+    }
+    *applied = Some(settings.clone());
+}
+
+// SETTINGS MENU
+
+// attached to all settings menu components for cleanup
+#[derive(Component, Clone, Copy)]
+pub struct CleanupSettingsMenu;
+
+// the settings as they were on entering the menu, restored on cancel
+#[derive(Resource)]
+pub struct SettingsBackup(pub Settings);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SettingsField {
+    Resolution,
+    Monitor,
+    Fullscreen,
+    Resizable,
+    Vsync,
+    Volume(VolumeKind),
+    Theme,
+}
+
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum SettingsButton {
+    Previous(SettingsField),
+    Next(SettingsField),
+    Confirm,
+    Cancel,
+}
+
+// the text showing a field's current value
+#[derive(Component, Clone, Copy)]
+pub struct SettingsValue(pub SettingsField);
+
+// clicking or dragging anywhere on the bar sets the volume
+#[derive(Component, Clone, Copy)]
+pub struct VolumeSlider(pub VolumeKind);
+
+#[derive(Component, Clone, Copy)]
+pub struct VolumeFill(pub VolumeKind);
+
+fn spawn_settings_menu(mut commands: Commands, theme: Res<Theme>, settings: Res<Settings>) {
+    commands.insert_resource(SettingsBackup(settings.clone()));
+    let rows = [
+        ("RESOLUTION", SettingsField::Resolution),
+        ("MONITOR", SettingsField::Monitor),
+        ("FULLSCREEN", SettingsField::Fullscreen),
+        ("RESIZABLE", SettingsField::Resizable),
+        ("VSYNC", SettingsField::Vsync),
+        ("MASTER", SettingsField::Volume(VolumeKind::Master)),
+        ("MUSIC", SettingsField::Volume(VolumeKind::Music)),
+        ("EFFECTS", SettingsField::Volume(VolumeKind::Effects)),
+        ("THEME", SettingsField::Theme),
+    ];
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            theme.background(ColorRole::Overlay),
+            GlobalZIndex(1),
+            CleanupSettingsMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::new("SETTINGS"), theme.text(FontRole::Title)));
+            for (index, (label, field)) in rows.into_iter().enumerate() {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(20.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(label),
+                            theme.text(FontRole::Body),
+                            Node {
+                                width: Val::Px(300.0),
+                                ..default()
+                            },
+                        ));
+                        spawn_button(
+                            row,
+                            &theme,
+                            ButtonWidth::Square,
+                            "<",
+                            (SettingsButton::Previous(field), Focusable::at(index, 0)),
+                        );
+                        let value_node = Node {
+                            width: Val::Px(400.0),
+                            height: Val::Px(40.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        };
+                        match field {
+                            SettingsField::Volume(kind) => {
+                                row.spawn((
+                                    value_node,
+                                    theme.background(ColorRole::Slider),
+                                    Interaction::default(),
+                                    RelativeCursorPosition::default(),
+                                    VolumeSlider(kind),
+                                ))
+                                .with_child((
+                                    Node {
+                                        position_type: PositionType::Absolute,
+                                        left: Val::Px(0.),
+                                        width: Val::Percent(settings.volume.get(kind) * 100.),
+                                        height: Val::Percent(100.),
+                                        ..default()
+                                    },
+                                    theme.background(ColorRole::SliderFill),
+                                    VolumeFill(kind),
+                                ));
+                            }
+                            _ => {
+                                row.spawn(value_node).with_child((
+                                    Text::new(""),
+                                    theme.text(FontRole::Body),
+                                    SettingsValue(field),
+                                ));
+                            }
+                        }
+                        spawn_button(
+                            row,
+                            &theme,
+                            ButtonWidth::Square,
+                            ">",
+                            (SettingsButton::Next(field), Focusable::at(index, 1)),
+                        );
+                    });
+            }
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (column, (label, action)) in [
+                        ("CONFIRM", SettingsButton::Confirm),
+                        ("CANCEL", SettingsButton::Cancel),
+                    ]
+                    .into_iter()
+                    .enumerate()
+                    {
+                        spawn_button(
+                            row,
+                            &theme,
+                            ButtonWidth::Theme,
+                            label,
+                            (action, Focusable::at(rows.len(), column)),
+                        );
+                    }
+                });
+        });
+    info!("[SPAWNED] Settings Menu Entities");
+}
+
+fn despawn_settings_menu(
+    mut commands: Commands,
+    query_settings_menu: Query<Entity, With<CleanupSettingsMenu>>,
+) {
+    for entity in query_settings_menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<SettingsBackup>();
+    info!("[DESPAWNED] Settings Menu Entities.");
+}
+
+// EVERY CHANGE IS APPLIED LIVE, ONLY CONFIRM WRITES settings.ron
+fn evr_settings_menu(
+    mut evr_menu_action: EventReader<MenuAction<SettingsButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<Settings>,
+    backup: Res<SettingsBackup>,
+    resolutions: Res<Resolutions>,
+    query_monitor: Query<&Monitor>,
+    mut evw_save_settings: EventWriter<SaveSettings>,
+    mut screens: Screens,
+) {
+    let mut pressed = evr_menu_action.read().last().map(|ev| **ev);
+    if keys.just_pressed(KeyCode::Escape) {
+        pressed = Some(SettingsButton::Cancel);
+    }
+    let Some(pressed) = pressed else {
+        return;
+    };
+
+    match pressed {
+        SettingsButton::Previous(field) | SettingsButton::Next(field) => {
+            let forward = matches!(pressed, SettingsButton::Next(_));
+            match field {
+                SettingsField::Resolution => {
+                    settings.resolution = resolutions.step(settings.resolution, forward);
+                }
+                SettingsField::Monitor => {
+                    let monitors = query_monitor.iter().count().max(1);
+                    settings.monitor = match forward {
+                        true => (settings.monitor + 1) % monitors,
+                        false => (settings.monitor + monitors - 1) % monitors,
+                    };
+                }
+                SettingsField::Fullscreen => settings.fullscreen = !settings.fullscreen,
+                SettingsField::Resizable => settings.resizable = !settings.resizable,
+                SettingsField::Vsync => settings.vsync = !settings.vsync,
+                SettingsField::Volume(kind) => {
+                    let step = if forward { VOLUME_STEP } else { -VOLUME_STEP };
+                    let value = settings.volume.get(kind) + step;
+                    settings.volume.set(kind, value);
+                }
+                // AN UNKNOWN THEME, E.G. HAND EDITED, STARTS FROM THE FIRST ONE
+                SettingsField::Theme => {
+                    let next = match THEMES.iter().position(|name| *name == settings.theme) {
+                        Some(i) if forward => (i + 1) % THEMES.len(),
+                        Some(i) => (i + THEMES.len() - 1) % THEMES.len(),
+                        None => 0,
+                    };
+                    settings.theme = THEMES[next].to_string();
+                }
+            }
+        }
+        SettingsButton::Confirm => {
+            evw_save_settings.send(SaveSettings);
+            info!("[EVENT] [WRITE] Save Settings.");
+            screens.pop();
+        }
+        SettingsButton::Cancel => {
+            // ONLY ASSIGN ON A REAL DIFFERENCE SO NOTHING IS RE-APPLIED NEEDLESSLY
+            if *settings != backup.0 {
+                *settings = backup.0.clone();
+            }
+            screens.pop();
+        }
+    }
+}
+
+fn volume_slider_system(
+    query_slider: Query<(&Interaction, &RelativeCursorPosition, &VolumeSlider)>,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, cursor, slider) in query_slider.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(normalized) = cursor.normalized else {
+            continue;
+        };
+        // ROUNDED TO WHOLE PERCENT SO DRAGGING DOESN'T DIRTY THE SETTINGS EVERY FRAME
+        let value = (normalized.x.clamp(0., 1.) * 100.).round() / 100.;
+        if settings.volume.get(slider.0) != value {
+            settings.volume.set(slider.0, value);
+        }
+    }
+}
+
+fn update_settings_menu(
+    settings: Res<Settings>,
+    resolutions: Res<Resolutions>,
+    mut query_value: Query<(&mut Text, &SettingsValue)>,
+    mut query_fill: Query<(&mut Node, &VolumeFill)>,
+) {
+    let on_off = |value: bool| match value {
+        true => "ON".to_string(),
+        false => "OFF".to_string(),
+    };
+    for (mut text, value) in query_value.iter_mut() {
+        **text = match value.0 {
+            SettingsField::Resolution => resolutions.label(settings.resolution),
+            SettingsField::Monitor => format!("{}", settings.monitor),
+            SettingsField::Fullscreen => on_off(settings.fullscreen),
+            SettingsField::Resizable => on_off(settings.resizable),
+            SettingsField::Vsync => on_off(settings.vsync),
+            SettingsField::Volume(kind) => format!("{:.0}%", settings.volume.get(kind) * 100.),
+            SettingsField::Theme => settings.theme.to_uppercase(),
+        };
+    }
+    for (mut node, fill) in query_fill.iter_mut() {
+        node.width = Val::Percent(settings.volume.get(fill.0) * 100.);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{fixture, read_versioned};
+
+    fn read(version: u32) -> Settings {
+        read_versioned::<Settings>(&fixture(&format!("settings_v{}.ron", version))).unwrap()
+    }
+
+    fn assert_volume(volume: Volume, expected: (f32, f32, f32)) {
+        assert_eq!((volume.master, volume.music, volume.effects), expected);
+    }
+
+    #[test]
+    fn settings_v0_migrates() {
+        let settings = read(0);
+        let defaults = Settings::default();
+        assert_eq!(settings.resolution, Vec2::new(1280., 720.));
+        assert_eq!(settings.monitor, 1);
+        assert_eq!(settings.seed, None);
+        assert_eq!(settings.autosave_interval, defaults.autosave_interval);
+        assert_eq!(settings.fullscreen, defaults.fullscreen);
+        assert_eq!(settings.resizable, defaults.resizable);
+        assert_eq!(settings.vsync, defaults.vsync);
+        assert!(settings.volume == defaults.volume);
+        assert_eq!(settings.theme, defaults.theme);
+    }
+
+    #[test]
+    fn settings_v1_migrates() {
+        let settings = read(1);
+        assert_eq!(settings.resolution, Vec2::new(1280., 720.));
+        assert_eq!(settings.monitor, 1);
+        assert_eq!(settings.seed, Some(7));
+        assert_eq!(
+            settings.autosave_interval,
+            Settings::default().autosave_interval
+        );
+    }
+
+    #[test]
+    fn settings_v2_migrates() {
+        let settings = read(2);
+        let defaults = Settings::default();
+        assert_eq!(settings.seed, Some(7));
+        assert_eq!(settings.autosave_interval, 30.);
+        assert_eq!(settings.fullscreen, defaults.fullscreen);
+        assert_eq!(settings.vsync, defaults.vsync);
+        assert!(settings.volume == defaults.volume);
+    }
+
+    #[test]
+    fn settings_v3_migrates() {
+        let settings = read(3);
+        assert_eq!(settings.autosave_interval, 30.);
+        assert!(settings.fullscreen);
+        assert!(!settings.vsync);
+        assert_volume(settings.volume, (0.5, 0.4, 0.3));
+        assert_eq!(settings.resizable, Settings::default().resizable);
+    }
+
+    #[test]
+    fn settings_v4_migrates() {
+        let settings = read(4);
+        assert_eq!(settings.resolution, Vec2::new(1280., 720.));
+        assert!(settings.fullscreen);
+        assert!(settings.resizable);
+        assert!(!settings.vsync);
+        assert_volume(settings.volume, (0.5, 0.4, 0.3));
+        assert_eq!(settings.theme, Settings::default().theme);
+    }
 }
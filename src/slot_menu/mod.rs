@@ -0,0 +1,220 @@
+use crate::{
+    notification::Notify,
+    save::{SaveGame, SaveMeta, SaveSlot, UserDirectory},
+    theme::{FontRole, Theme},
+    ui::{MenuAction, MenuAppExt, MenuBuilder, Screens},
+    AppState,
+};
+use bevy::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct SlotMenuPlugin;
+impl Plugin for SlotMenuPlugin {
+    fn name(&self) -> &str {
+        "Slot Menu Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_menu_action::<SlotMenuButton>()
+            .add_systems(OnEnter(AppState::SaveSlots), read_slots)
+            .add_systems(
+                Update,
+                (
+                    spawn_slot_menu.run_if(resource_changed::<SlotMenu>),
+                    evr_slot_menu,
+                    escape_slot_menu,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::SaveSlots)),
+            )
+            .add_systems(OnExit(AppState::SaveSlots), despawn_slot_menu);
+    }
+}
+
+// attached to all slot menu components for cleanup
+#[derive(Component, Clone, Copy)]
+pub struct CleanupSlotMenu;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum SlotMenuButton {
+    Load(SaveSlot),
+    New(SaveSlot),
+    Overwrite(SaveSlot),
+    Delete(SaveSlot),
+    Confirm,
+    Cancel,
+    Back,
+}
+
+// headers read on entering the screen, the screen is rebuilt whenever this changes
+#[derive(Resource, Default)]
+pub struct SlotMenu {
+    pub metas: Vec<Option<SaveMeta>>,
+    pub confirm: Option<SlotMenuButton>, // overwrite or delete waiting on a yes/no
+}
+
+fn read_slots(mut commands: Commands, user_dir: Res<UserDirectory>) {
+    commands.insert_resource(SlotMenu {
+        metas: SaveSlot::read_all(&user_dir),
+        confirm: None,
+    });
+}
+
+fn spawn_slot_menu(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    slot_menu: Res<SlotMenu>,
+    query_slot_menu: Query<Entity, With<CleanupSlotMenu>>,
+) {
+    for entity in query_slot_menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    // A PENDING OVERWRITE OR DELETE REPLACES THE LIST UNTIL ANSWERED
+    let menu = match slot_menu.confirm {
+        Some(pending) => {
+            let question = match pending {
+                SlotMenuButton::Overwrite(slot) => format!("OVERWRITE SLOT {}?", slot.0 + 1),
+                SlotMenuButton::Delete(slot) => format!("DELETE SLOT {}?", slot.0 + 1),
+                _ => String::new(),
+            };
+            MenuBuilder::new().text(question, FontRole::Heading).row([
+                ("YES", SlotMenuButton::Confirm),
+                ("NO", SlotMenuButton::Cancel),
+            ])
+        }
+        None => {
+            let mut menu = MenuBuilder::new().title("SAVES");
+            for (slot, meta) in SaveSlot::all().zip(slot_menu.metas.iter()) {
+                menu = match meta {
+                    Some(meta) => menu.labelled_row(
+                        describe(slot, meta),
+                        [
+                            ("LOAD", SlotMenuButton::Load(slot)),
+                            ("OVERWRITE", SlotMenuButton::Overwrite(slot)),
+                            ("DELETE", SlotMenuButton::Delete(slot)),
+                        ],
+                    ),
+                    None => menu.labelled_row(
+                        format!("SLOT {}: EMPTY", slot.0 + 1),
+                        [("NEW", SlotMenuButton::New(slot))],
+                    ),
+                };
+            }
+            menu.row([("BACK", SlotMenuButton::Back)])
+        }
+    };
+    menu.spawn(&mut commands, &theme, CleanupSlotMenu);
+    info!("[SPAWNED] Slot Menu Entities");
+}
+
+// SLOT 1: OLD MAN - FOREST - 1234M - 0:12:03 - 5 MIN AGO
+fn describe(slot: SaveSlot, meta: &SaveMeta) -> String {
+    let play_time = meta.play_time as u64;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let age = now.saturating_sub(meta.timestamp);
+    let age = match age {
+        0..60 => "JUST NOW".to_string(),
+        60..3_600 => format!("{} MIN AGO", age / 60),
+        3_600..86_400 => format!("{} H AGO", age / 3_600),
+        _ => format!("{} DAYS AGO", age / 86_400),
+    };
+    format!(
+        "SLOT {}: {} - {} - {:.0}M - {}:{:02}:{:02} - {}",
+        slot.0 + 1,
+        meta.party.join(", "),
+        meta.area,
+        meta.distance,
+        play_time / 3_600,
+        play_time / 60 % 60,
+        play_time % 60,
+        age
+    )
+}
+
+fn despawn_slot_menu(
+    mut commands: Commands,
+    query_slot_menu: Query<Entity, With<CleanupSlotMenu>>,
+) {
+    for entity in query_slot_menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<SlotMenu>();
+    info!("[DESPAWNED] Slot Menu Entities.");
+}
+
+fn evr_slot_menu(
+    mut commands: Commands,
+    mut evr_menu_action: EventReader<MenuAction<SlotMenuButton>>,
+    mut slot_menu: ResMut<SlotMenu>,
+    user_dir: Res<UserDirectory>,
+    mut screens: Screens,
+    mut evw_notify: EventWriter<Notify>,
+) {
+    for ev in evr_menu_action.read() {
+        match **ev {
+            SlotMenuButton::Load(slot) => {
+                match SaveGame::queue_restore(&mut commands, &user_dir, slot) {
+                    Ok(_) => screens.goto(AppState::Playing),
+                    Err(e) => {
+                        error!("[ERROR] Could not load {}. \n{}", slot.save_file(), e);
+                        evw_notify.send(Notify::error(format!("LOAD FAILED: {}", e)));
+                    }
+                }
+            }
+            SlotMenuButton::New(slot) => {
+                commands.insert_resource(slot);
+                screens.goto(AppState::Playing);
+            }
+            SlotMenuButton::Overwrite(_) | SlotMenuButton::Delete(_) => {
+                slot_menu.confirm = Some(**ev);
+            }
+            SlotMenuButton::Confirm => {
+                let Some(pending) = slot_menu.confirm.take() else {
+                    continue;
+                };
+                let (SlotMenuButton::Overwrite(slot) | SlotMenuButton::Delete(slot)) = pending
+                else {
+                    continue;
+                };
+                if let Err(e) = SaveGame::delete(&user_dir, slot) {
+                    error!("[ERROR] Could not delete {}. \n{}", slot.save_file(), e);
+                    evw_notify.send(Notify::error(format!("DELETE FAILED: {}", e)));
+                    continue;
+                }
+                info!("[DELETED] SaveGame: Slot {}", slot.0);
+                // OVERWRITING STARTS A FRESH RUN IN THE NOW EMPTY SLOT
+                if let SlotMenuButton::Overwrite(_) = pending {
+                    commands.insert_resource(slot);
+                    screens.goto(AppState::Playing);
+                } else {
+                    slot_menu.metas = SaveSlot::read_all(&user_dir);
+                }
+            }
+            SlotMenuButton::Cancel => {
+                slot_menu.confirm = None;
+            }
+            SlotMenuButton::Back => {
+                screens.pop();
+            }
+        }
+    }
+}
+
+// ESCAPE BACKS OUT OF A CONFIRMATION FIRST, THEN OUT OF THE SCREEN
+fn escape_slot_menu(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut slot_menu: ResMut<SlotMenu>,
+    mut screens: Screens,
+) {
+    if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    if slot_menu.confirm.is_some() {
+        slot_menu.confirm = None;
+    } else {
+        screens.pop();
+    }
+}
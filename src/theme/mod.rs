@@ -0,0 +1,477 @@
+use crate::{loading::RonLoaderError, notification::Notify, settings::Settings};
+use bevy::{
+    asset::{io::Reader, AssetLoadFailedEvent, AssetLoader, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
+
+pub struct ThemePlugin;
+impl Plugin for ThemePlugin {
+    fn name(&self) -> &str {
+        "Theme Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Theme>()
+            .register_asset_loader(ThemeLoader)
+            .init_resource::<Theme>()
+            .init_resource::<ActiveTheme>()
+            .add_systems(
+                Update,
+                (
+                    load_theme.run_if(resource_changed::<Settings>),
+                    evr_theme_loaded,
+                    (restyle_ui, restyle_images).run_if(resource_changed::<Theme>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+// THE NAMES A THEME CAN BE SWITCHED TO, EACH IS assets/themes/<name>.theme.ron
+pub const THEMES: [&str; 2] = ["default", "light"];
+pub const FALLBACK_FONT: &str = "fonts/PixelifySans-Regular.ttf";
+
+fn theme_path(name: &str) -> String {
+    format!("themes/{}.theme.ron", name)
+}
+
+// the theme every ui spawner reads, replaced whenever the active theme (re)loads
+#[derive(Asset, Resource, TypePath, Clone)]
+pub struct Theme {
+    pub font: Handle<Font>,
+    pub font_sizes: FontSizes,
+    pub button: ButtonStyle,
+    pub colors: ThemeColors,
+}
+// USED UNTIL THE THEME FILE HAS LOADED, MATCHES themes/default.theme.ron
+impl FromWorld for Theme {
+    fn from_world(world: &mut World) -> Self {
+        let gray = |value: u8| Color::srgb_u8(value, value, value);
+        Self {
+            font: world.resource::<AssetServer>().load(FALLBACK_FONT),
+            font_sizes: FontSizes {
+                title: 100.,
+                heading: 60.,
+                body: 40.,
+                button: 33.,
+                small: 30.,
+            },
+            button: ButtonStyle {
+                width: 220.,
+                height: 65.,
+                border: 5.,
+                radius: 32.5,
+            },
+            colors: ThemeColors {
+                button_background: StateColors {
+                    default: gray(54),
+                    hovered: gray(211),
+                    pressed: gray(167),
+                },
+                button_border: StateColors {
+                    default: Color::BLACK,
+                    hovered: Color::BLACK,
+                    pressed: Color::BLACK,
+                },
+                button_text: StateColors {
+                    default: gray(211),
+                    hovered: gray(93),
+                    pressed: Color::WHITE,
+                },
+                text: Color::WHITE,
+                overlay: Color::srgba(0., 0., 0., 0.7),
+                slider: gray(54),
+                slider_fill: gray(211),
+                info: gray(211),
+                error: Color::srgb(0.9, 0.3, 0.3),
+                empty_heart: Color::srgba(0.1, 0.1, 0.1, 0.6),
+            },
+        }
+    }
+}
+impl Theme {
+    pub fn font_size(&self, role: FontRole) -> f32 {
+        match role {
+            FontRole::Title => self.font_sizes.title,
+            FontRole::Heading => self.font_sizes.heading,
+            FontRole::Body => self.font_sizes.body,
+            FontRole::Button => self.font_sizes.button,
+            FontRole::Small => self.font_sizes.small,
+        }
+    }
+
+    pub fn color(&self, role: ColorRole) -> Color {
+        match role {
+            ColorRole::Text => self.colors.text,
+            ColorRole::Overlay => self.colors.overlay,
+            ColorRole::Slider => self.colors.slider,
+            ColorRole::SliderFill => self.colors.slider_fill,
+            ColorRole::Info => self.colors.info,
+            ColorRole::Error => self.colors.error,
+            ColorRole::EmptyHeart => self.colors.empty_heart,
+        }
+    }
+
+    pub fn text_font(&self, role: FontRole) -> (TextFont, ThemedFont) {
+        let text_font = TextFont {
+            font: self.font.clone(),
+            font_size: self.font_size(role),
+            ..default()
+        };
+        (text_font, ThemedFont(role))
+    }
+
+    // PLAIN TEXT, BUTTON LABELS ARE COLOURED BY THEIR BUTTON INSTEAD
+    pub fn text(&self, role: FontRole) -> impl Bundle {
+        (
+            self.text_font(role),
+            TextColor(self.colors.text),
+            ThemedTextColor(ColorRole::Text),
+        )
+    }
+
+    pub fn background(&self, role: ColorRole) -> impl Bundle {
+        (BackgroundColor(self.color(role)), ThemedBackground(role))
+    }
+
+    // TINTS THE WHOLE IMAGE
+    pub fn image(&self, image: Handle<Image>, role: ColorRole) -> impl Bundle {
+        (
+            ImageNode::new(image).with_color(self.color(role)),
+            ThemedImage(role),
+        )
+    }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct FontSizes {
+    pub title: f32,
+    pub heading: f32,
+    pub body: f32,
+    pub button: f32,
+    pub small: f32,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct ButtonStyle {
+    pub width: f32,
+    pub height: f32,
+    pub border: f32,
+    pub radius: f32,
+}
+
+#[derive(Clone, Copy)]
+pub struct StateColors {
+    pub default: Color,
+    pub hovered: Color, // also the keyboard focus highlight
+    pub pressed: Color,
+}
+impl StateColors {
+    pub fn get(&self, interaction: Interaction) -> Color {
+        match interaction {
+            Interaction::Pressed => self.pressed,
+            Interaction::Hovered => self.hovered,
+            Interaction::None => self.default,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ThemeColors {
+    pub button_background: StateColors,
+    pub button_border: StateColors,
+    pub button_text: StateColors,
+    pub text: Color,
+    pub overlay: Color,
+    pub slider: Color,
+    pub slider_fill: Color,
+    pub info: Color,
+    pub error: Color,
+    pub empty_heart: Color,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FontRole {
+    Title,
+    Heading,
+    Body,
+    Button,
+    Small,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColorRole {
+    Text,
+    Overlay,
+    Slider,
+    SliderFill,
+    Info,
+    Error,
+    EmptyHeart,
+}
+
+// markers so a theme swap can restyle what is already on screen
+#[derive(Component, Clone, Copy)]
+pub struct ThemedFont(pub FontRole);
+
+#[derive(Component, Clone, Copy)]
+pub struct ThemedTextColor(pub ColorRole);
+
+#[derive(Component, Clone, Copy)]
+pub struct ThemedBackground(pub ColorRole);
+
+#[derive(Component, Clone, Copy)]
+pub struct ThemedImage(pub ColorRole);
+
+// how a button's width follows the theme, Fixed keeps its own on restyle
+#[derive(Component, Clone, Copy)]
+pub enum ButtonWidth {
+    Theme,
+    Square, // as wide as the theme's button height
+    Fixed(f32),
+}
+impl ButtonWidth {
+    pub fn px(self, theme: &Theme) -> f32 {
+        match self {
+            Self::Theme => theme.button.width,
+            Self::Square => theme.button.height,
+            Self::Fixed(width) => width,
+        }
+    }
+}
+
+// the theme named in settings, its handle keeps it loaded
+#[derive(Resource, Default)]
+pub struct ActiveTheme {
+    pub name: String,
+    pub handle: Handle<Theme>,
+}
+
+// THEME FILE
+
+#[derive(Deserialize)]
+struct ThemeFile {
+    palette: String,
+    font: String,
+    font_sizes: FontSizes,
+    button: ButtonStyle,
+    colors: ThemeColorsFile,
+}
+
+// a colour picked from the palette image, or spelled out when the palette lacks it
+#[derive(Deserialize, Clone, Copy)]
+enum PaletteColor {
+    Palette(usize),
+    Faded(usize, f32),
+    Srgb(f32, f32, f32),
+}
+impl PaletteColor {
+    fn resolve(self, palette: &[Color]) -> Result<Color, RonLoaderError> {
+        let pick = |index: usize| {
+            palette.get(index).copied().ok_or_else(|| {
+                RonLoaderError::Invalid(format!(
+                    "palette index {} out of range, palette has {} colours",
+                    index,
+                    palette.len()
+                ))
+            })
+        };
+        match self {
+            Self::Palette(index) => pick(index),
+            Self::Faded(index, alpha) => Ok(pick(index)?.with_alpha(alpha)),
+            Self::Srgb(r, g, b) => Ok(Color::srgb(r, g, b)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct StateColorsFile {
+    default: PaletteColor,
+    hovered: PaletteColor,
+    pressed: PaletteColor,
+}
+impl StateColorsFile {
+    fn resolve(&self, palette: &[Color]) -> Result<StateColors, RonLoaderError> {
+        Ok(StateColors {
+            default: self.default.resolve(palette)?,
+            hovered: self.hovered.resolve(palette)?,
+            pressed: self.pressed.resolve(palette)?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ThemeColorsFile {
+    button_background: StateColorsFile,
+    button_border: StateColorsFile,
+    button_text: StateColorsFile,
+    text: PaletteColor,
+    overlay: PaletteColor,
+    slider: PaletteColor,
+    slider_fill: PaletteColor,
+    info: PaletteColor,
+    error: PaletteColor,
+    empty_heart: PaletteColor,
+}
+impl ThemeColorsFile {
+    fn resolve(&self, palette: &[Color]) -> Result<ThemeColors, RonLoaderError> {
+        Ok(ThemeColors {
+            button_background: self.button_background.resolve(palette)?,
+            button_border: self.button_border.resolve(palette)?,
+            button_text: self.button_text.resolve(palette)?,
+            text: self.text.resolve(palette)?,
+            overlay: self.overlay.resolve(palette)?,
+            slider: self.slider.resolve(palette)?,
+            slider_fill: self.slider_fill.resolve(palette)?,
+            info: self.info.resolve(palette)?,
+            error: self.error.resolve(palette)?,
+            empty_heart: self.empty_heart.resolve(palette)?,
+        })
+    }
+}
+
+struct ThemeLoader;
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Theme, RonLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: ThemeFile = ron::de::from_bytes(&bytes)?;
+
+        // THE PALETTE IS READ HERE, EDITING THE IMAGE RELOADS THE THEME
+        let palette = load_context
+            .loader()
+            .immediate()
+            .load::<Image>(file.palette.as_str())
+            .await
+            .map_err(|e| RonLoaderError::Invalid(e.to_string()))?;
+        let palette = palette.get();
+        let palette = (0..palette.width())
+            .map(|x| palette.get_color_at(x, 0))
+            .collect::<Result<Vec<Color>, _>>()
+            .map_err(|e| RonLoaderError::Invalid(format!("{:?}", e)))?;
+
+        Ok(Theme {
+            font: load_context.load(file.font),
+            font_sizes: file.font_sizes,
+            button: file.button,
+            colors: file.colors.resolve(&palette)?,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+// SYSTEMS
+
+fn load_theme(
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    themes: Res<Assets<Theme>>,
+    mut active_theme: ResMut<ActiveTheme>,
+    mut theme: ResMut<Theme>,
+) {
+    if active_theme.name == settings.theme {
+        return;
+    }
+    active_theme.name = settings.theme.clone();
+    active_theme.handle = asset_server.load(theme_path(&settings.theme));
+    // SWITCHING BACK TO A THEME STILL IN MEMORY SENDS NO LOAD EVENT
+    if let Some(loaded) = themes.get(&active_theme.handle) {
+        *theme = loaded.clone();
+        info!("[MODIFIED] Theme >> {}", active_theme.name);
+    }
+}
+
+fn evr_theme_loaded(
+    mut evr_asset: EventReader<AssetEvent<Theme>>,
+    mut evr_failed: EventReader<AssetLoadFailedEvent<Theme>>,
+    themes: Res<Assets<Theme>>,
+    active_theme: Res<ActiveTheme>,
+    mut theme: ResMut<Theme>,
+    mut evw_notify: EventWriter<Notify>,
+) {
+    for ev in evr_asset.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = ev else {
+            continue;
+        };
+        if *id != active_theme.handle.id() {
+            continue;
+        }
+        if let Some(loaded) = themes.get(*id) {
+            *theme = loaded.clone();
+            info!("[MODIFIED] Theme >> {}", active_theme.name);
+        }
+    }
+    // THE PREVIOUS THEME STAYS UP
+    for ev in evr_failed.read() {
+        error!("[ERROR] Could not load {}. \n{}", ev.path, ev.error);
+        evw_notify.send(Notify::error(format!("THEME NOT LOADED: {}", ev.path)));
+    }
+}
+
+// HOT SWAP, EVERYTHING ALREADY SPAWNED TAKES ON THE NEW THEME
+fn restyle_ui(
+    theme: Res<Theme>,
+    mut query_font: Query<(&mut TextFont, &ThemedFont)>,
+    mut query_text_color: Query<(&mut TextColor, &ThemedTextColor)>,
+    mut query_background: Query<(&mut BackgroundColor, &ThemedBackground), Without<Button>>,
+    mut query_button: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            &mut BorderColor,
+            &Children,
+        ),
+        With<Button>,
+    >,
+    mut query_button_shape: Query<
+        (&mut Node, &mut BorderRadius, Option<&ButtonWidth>),
+        With<Button>,
+    >,
+    mut query_label: Query<&mut TextColor, Without<ThemedTextColor>>,
+) {
+    for (mut text_font, themed) in query_font.iter_mut() {
+        text_font.font = theme.font.clone();
+        text_font.font_size = theme.font_size(themed.0);
+    }
+    for (mut text_color, themed) in query_text_color.iter_mut() {
+        text_color.0 = theme.color(themed.0);
+    }
+    for (mut background_color, themed) in query_background.iter_mut() {
+        background_color.0 = theme.color(themed.0);
+    }
+    for (interaction, mut background_color, mut border_color, children) in query_button.iter_mut() {
+        background_color.0 = theme.colors.button_background.get(*interaction);
+        border_color.0 = theme.colors.button_border.get(*interaction);
+        if let Ok(mut text_color) = query_label.get_mut(children[0]) {
+            text_color.0 = theme.colors.button_text.get(*interaction);
+        }
+    }
+    for (mut node, mut radius, width) in query_button_shape.iter_mut() {
+        if let Some(width) = width {
+            node.width = Val::Px(width.px(&theme));
+        }
+        node.height = Val::Px(theme.button.height);
+        node.border = UiRect::all(Val::Px(theme.button.border));
+        *radius = BorderRadius::all(Val::Px(theme.button.radius));
+    }
+    info!("[MODIFIED] UI Restyled");
+}
+
+fn restyle_images(theme: Res<Theme>, mut query_image: Query<(&mut ImageNode, &ThemedImage)>) {
+    for (mut image, themed) in query_image.iter_mut() {
+        image.color = theme.color(themed.0);
+    }
+}
@@ -1,5 +1,10 @@
-use crate::AppState;
-use bevy::prelude::*;
+use crate::{
+    notification::Notify,
+    save::{SaveGame, SaveSlot, UserDirectory},
+    theme::{ButtonWidth, FontRole, Theme},
+    AppState, PauseState,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, ui::UiSystem};
 
 pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
@@ -8,80 +13,50 @@ impl Plugin for MenuPlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Menu), spawn_main_menu)
-            .add_systems(Update, escape_to_menu.run_if(in_state(AppState::Settings)))
-            .add_systems(Update, menu_button_system.run_if(in_state(AppState::Menu)))
+        app.init_resource::<Focus>()
+            .add_systems(
+                PreUpdate,
+                (release_focus, navigate_focus, activate_focus)
+                    .chain()
+                    .after(UiSystem::Focus),
+            )
+            .add_systems(PostUpdate, highlight_focus)
+            .add_systems(OnEnter(AppState::Menu), spawn_main_menu)
+            .init_resource::<ScreenStack>()
+            .add_menu_action::<MainMenuButton>()
+            .add_systems(Update, evr_main_menu.run_if(in_state(AppState::Menu)))
             .add_systems(OnExit(AppState::Menu), despawn_main_menu);
     }
 }
 
-// NOTE: THESE ARE NORMALIZED 0.0-1.0
-const BUTTON_BACKGROUND_COLOR_DEFAULT: Color = Color::srgb(0.2235, 0.2235, 0.2235);
-const BUTTON_BACKGROUND_COLOR_PRESSED: Color = Color::srgb(0.6549, 0.6549, 0.6549);
-const BUTTON_BACKGROUND_COLOR_HOVERED: Color = Color::srgb(0.7804, 0.7804, 0.7804);
-
-const BUTTON_BORDER_COLOR_DEFAULT: Color = Color::srgb(0.0, 0.0, 0.0);
-const BUTTON_BORDER_COLOR_PRESSED: Color = Color::srgb(0.0, 0.0, 0.0);
-const BUTTON_BORDER_COLOR_HOVERED: Color = Color::srgb(0.0, 0.0, 0.0);
-
-const BUTTON_TEXT_COLOR_DEFAULT: Color = Color::srgb(0.7804, 0.7804, 0.7804);
-const BUTTON_TEXT_COLOR_PRESSED: Color = Color::srgb(1.0, 1.0, 1.0);
-const BUTTON_TEXT_COLOR_HOVERED: Color = Color::srgb(0.3529, 0.3529, 0.3529);
-
 // attached to all main menu components for cleanup
 #[derive(Component, Clone, Copy)]
 pub struct CleanupMainMenu;
 
 #[derive(Component, Clone, Copy)]
 pub enum MainMenuButton {
+    Continue,
     Play,
+    Saves,
     Settings,
     Exit,
 }
 
-fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let parent_node = Node {
-        width: Val::Percent(100.0),
-        height: Val::Percent(100.0),
-        align_items: AlignItems::Center,
-        justify_content: JustifyContent::SpaceEvenly,
-        ..default()
-    };
-    let child_node = Node {
-        width: Val::Px(150.0),
-        height: Val::Px(65.0),
-        border: UiRect::all(Val::Px(5.0)),
-        justify_content: JustifyContent::Center,
-        align_items: AlignItems::Center,
-        ..default()
-    };
-    commands.spawn(parent_node).with_children(|parent| {
-        for i in 0..3 {
-            let text: Text = match i {
-                0 => Text::new("PLAY"),
-                1 => Text::new("SETTINGS"),
-                _ => Text::new("EXIT"),
-            };
-            parent
-                .spawn((
-                    Button,
-                    MainMenuButton::Play,
-                    child_node.clone(),
-                    BorderColor(Color::BLACK),
-                    BorderRadius::MAX,
-                    BackgroundColor(BUTTON_BACKGROUND_COLOR_DEFAULT),
-                ))
-                .with_child((
-                    text,
-                    TextFont {
-                        font: asset_server.load("fonts/PixelifySans-Regular.ttf"),
-                        font_size: 33.0,
-                        ..default()
-                    },
-                    TextColor(BUTTON_TEXT_COLOR_DEFAULT),
-                ));
-        }
-    });
+fn spawn_main_menu(mut commands: Commands, theme: Res<Theme>, user_dir: Res<UserDirectory>) {
+    // CONTINUE ONLY SHOWS UP WHEN THERE IS A RUN TO CONTINUE
+    let mut actions = vec![];
+    if SaveSlot::most_recent(&user_dir).is_some() {
+        actions.push(("CONTINUE", MainMenuButton::Continue));
+    }
+    actions.extend([
+        ("PLAY", MainMenuButton::Play),
+        ("SAVES", MainMenuButton::Saves),
+        ("SETTINGS", MainMenuButton::Settings),
+        ("EXIT", MainMenuButton::Exit),
+    ]);
+    MenuBuilder::new()
+        .row(actions)
+        .spawn(&mut commands, &theme, CleanupMainMenu);
     info!("[SPAWNED] Main Menu Entities");
 }
 
@@ -95,75 +70,506 @@ fn despawn_main_menu(
     }
 }
 
-fn menu_button_system(
+fn evr_main_menu(
+    mut commands: Commands,
+    mut evr_menu_action: EventReader<MenuAction<MainMenuButton>>,
+    mut screens: Screens,
+    user_dir: Res<UserDirectory>,
+    mut evw_notify: EventWriter<Notify>,
+) {
+    for ev in evr_menu_action.read() {
+        match **ev {
+            MainMenuButton::Continue => {
+                let Some(slot) = SaveSlot::most_recent(&user_dir) else {
+                    continue;
+                };
+                match SaveGame::queue_restore(&mut commands, &user_dir, slot) {
+                    Ok(_) => screens.goto(AppState::Playing),
+                    Err(e) => {
+                        error!("[ERROR] Could not load {}. \n{}", slot.save_file(), e);
+                        evw_notify.send(Notify::error(format!("LOAD FAILED: {}", e)));
+                    }
+                }
+            }
+            // EVERY SLOT TAKEN, LET THE PLAYER PICK ONE TO OVERWRITE
+            MainMenuButton::Play => match SaveSlot::first_empty(&user_dir) {
+                Some(slot) => {
+                    commands.insert_resource(slot);
+                    screens.goto(AppState::Playing);
+                }
+                None => screens.push(AppState::SaveSlots),
+            },
+            MainMenuButton::Saves => screens.push(AppState::SaveSlots),
+            MainMenuButton::Settings => screens.push(AppState::Settings),
+            MainMenuButton::Exit => screens.goto(AppState::Exit),
+        }
+    }
+}
+
+// MENU BUILDER
+
+pub const MENU_LABEL_WIDTH: f32 = 800.;
+
+// sent when a button spawned with action A is pressed, by mouse or focus
+#[derive(Event, Clone, Copy, Deref)]
+pub struct MenuAction<A: Send + Sync + 'static>(pub A);
+
+pub trait MenuAppExt {
+    // STYLES AND DISPATCHES EVERY BUTTON CARRYING AN A
+    fn add_menu_action<A: Component + Copy>(&mut self) -> &mut Self;
+}
+impl MenuAppExt for App {
+    fn add_menu_action<A: Component + Copy>(&mut self) -> &mut Self {
+        self.add_event::<MenuAction<A>>().add_systems(
+            PreUpdate,
+            menu_action_system::<A>
+                .after(UiSystem::Focus)
+                .after(activate_focus),
+        )
+    }
+}
+
+enum MenuItem<A> {
+    Text(String, FontRole),
+    Row(Option<String>, Vec<(String, A)>),
+}
+
+// a column of text lines and button rows, each row is one line of the focus order
+pub struct MenuBuilder<A> {
+    items: Vec<MenuItem<A>>,
+    button_width: Option<f32>, // the theme's button width unless set
+}
+impl<A: Component + Copy> Default for MenuBuilder<A> {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            button_width: None,
+        }
+    }
+}
+impl<A: Component + Copy> MenuBuilder<A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(self, title: impl Into<String>) -> Self {
+        self.text(title, FontRole::Title)
+    }
+
+    pub fn text(mut self, text: impl Into<String>, role: FontRole) -> Self {
+        self.items.push(MenuItem::Text(text.into(), role));
+        self
+    }
+
+    pub fn row<S: Into<String>>(mut self, actions: impl IntoIterator<Item = (S, A)>) -> Self {
+        let actions = actions.into_iter().map(|(l, a)| (l.into(), a)).collect();
+        self.items.push(MenuItem::Row(None, actions));
+        self
+    }
+
+    // A ROW LED BY A FIXED WIDTH LABEL SO THE BUTTONS LINE UP
+    pub fn labelled_row<S: Into<String>>(
+        mut self,
+        label: impl Into<String>,
+        actions: impl IntoIterator<Item = (S, A)>,
+    ) -> Self {
+        let actions = actions.into_iter().map(|(l, a)| (l.into(), a)).collect();
+        self.items.push(MenuItem::Row(Some(label.into()), actions));
+        self
+    }
+
+    pub fn button_width(mut self, button_width: f32) -> Self {
+        self.button_width = Some(button_width);
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands, theme: &Theme, cleanup: impl Component) -> Entity {
+        let button_width = self
+            .button_width
+            .map_or(ButtonWidth::Theme, ButtonWidth::Fixed);
+        let mut row_index = 0;
+        commands
+            .spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(30.0),
+                    ..default()
+                },
+                cleanup,
+            ))
+            .with_children(|parent| {
+                for item in self.items {
+                    match item {
+                        MenuItem::Text(text, role) => {
+                            parent.spawn((Text::new(text), theme.text(role)));
+                        }
+                        MenuItem::Row(label, actions) => {
+                            parent
+                                .spawn(Node {
+                                    flex_direction: FlexDirection::Row,
+                                    align_items: AlignItems::Center,
+                                    column_gap: Val::Px(20.0),
+                                    ..default()
+                                })
+                                .with_children(|row| {
+                                    if let Some(label) = label {
+                                        row.spawn((
+                                            Text::new(label),
+                                            theme.text(FontRole::Small),
+                                            Node {
+                                                width: Val::Px(MENU_LABEL_WIDTH),
+                                                ..default()
+                                            },
+                                        ));
+                                    }
+                                    for (column, (label, action)) in actions.into_iter().enumerate()
+                                    {
+                                        spawn_button(
+                                            row,
+                                            theme,
+                                            button_width,
+                                            &label,
+                                            (action, Focusable::at(row_index, column)),
+                                        );
+                                    }
+                                });
+                            row_index += 1;
+                        }
+                    }
+                }
+            })
+            .id()
+    }
+}
+
+// A STYLED BUTTON FOR SCREENS THAT LAY THEMSELVES OUT, `extra` CARRIES THE ACTION
+pub fn spawn_button(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    width: ButtonWidth,
+    label: &str,
+    extra: impl Bundle,
+) -> Entity {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(width.px(theme)),
+                height: Val::Px(theme.button.height),
+                border: UiRect::all(Val::Px(theme.button.border)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BorderColor(theme.colors.button_border.default),
+            BorderRadius::all(Val::Px(theme.button.radius)),
+            BackgroundColor(theme.colors.button_background.default),
+            width,
+            extra,
+        ))
+        .with_child((
+            Text::new(label),
+            theme.text_font(FontRole::Button),
+            TextColor(theme.colors.button_text.default),
+        ))
+        .id()
+}
+
+fn menu_action_system<A: Component + Copy>(
     mut interaction_query: Query<
         (
             &Interaction,
             &mut BackgroundColor,
             &mut BorderColor,
             &Children,
-            &MainMenuButton,
+            &A,
         ),
-        (Changed<Interaction>, With<MainMenuButton>),
+        Changed<Interaction>,
     >,
     mut text_color_query: Query<&mut TextColor>,
-    current_state: Res<State<AppState>>,
-    mut next_state: ResMut<NextState<AppState>>,
+    mut evw_menu_action: EventWriter<MenuAction<A>>,
+    theme: Res<Theme>,
 ) {
-    for (interaction, mut background_color, mut border_color, children, mmb) in
+    for (interaction, mut background_color, mut border_color, children, action) in
         &mut interaction_query
     {
-        let mut text_color = text_color_query.get_mut(children[0]).unwrap();
-        match *interaction {
-            Interaction::Pressed => {
-                *text_color = BUTTON_TEXT_COLOR_PRESSED.into();
-                *background_color = BUTTON_BACKGROUND_COLOR_PRESSED.into();
-                border_color.0 = BUTTON_BORDER_COLOR_PRESSED.into();
-
-                match mmb {
-                    MainMenuButton::Play => {
-                        if current_state.get() == &AppState::Menu {
-                            next_state.set(AppState::Playing);
-                        }
-                    }
-                    MainMenuButton::Settings => {
-                        if current_state.get() == &AppState::Menu {
-                            next_state.set(AppState::Settings);
-                        }
-                    }
-                    MainMenuButton::Exit => {
-                        if current_state.get() == &AppState::Menu {
-                            next_state.set(AppState::Exit);
-                        }
-                    }
-                }
-            }
-            Interaction::Hovered => {
-                *text_color = BUTTON_TEXT_COLOR_HOVERED.into();
-                *background_color = BUTTON_BACKGROUND_COLOR_HOVERED.into();
-                border_color.0 = BUTTON_BORDER_COLOR_HOVERED.into();
+        if *interaction == Interaction::Pressed {
+            evw_menu_action.send(MenuAction(*action));
+        }
+        background_color.0 = theme.colors.button_background.get(*interaction);
+        border_color.0 = theme.colors.button_border.get(*interaction);
+        if let Ok(mut text_color) = text_color_query.get_mut(children[0]) {
+            text_color.0 = theme.colors.button_text.get(*interaction);
+        }
+    }
+}
+
+// SCREEN STACK
+
+// a menu screen, either a whole app state or a layer over a paused run
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Screen {
+    App(AppState),
+    Pause(PauseState),
+}
+impl From<AppState> for Screen {
+    fn from(state: AppState) -> Self {
+        Self::App(state)
+    }
+}
+impl From<PauseState> for Screen {
+    fn from(state: PauseState) -> Self {
+        Self::Pause(state)
+    }
+}
+
+// the screens under the current one, most recent last
+#[derive(Resource, Default)]
+pub struct ScreenStack(Vec<Screen>);
+
+#[derive(SystemParam)]
+pub struct Screens<'w> {
+    stack: ResMut<'w, ScreenStack>,
+    app_state: Res<'w, State<AppState>>,
+    pause_state: Option<Res<'w, State<PauseState>>>, // only exists while playing
+    next_app_state: ResMut<'w, NextState<AppState>>,
+    next_pause_state: ResMut<'w, NextState<PauseState>>,
+}
+impl Screens<'_> {
+    pub fn current(&self) -> Screen {
+        match self.pause_state.as_ref().map(|state| *state.get()) {
+            Some(state) if state != PauseState::Running => Screen::Pause(state),
+            _ => Screen::App(*self.app_state.get()),
+        }
+    }
+
+    // OPENS A SUBMENU, POP COMES BACK TO THE CURRENT SCREEN
+    pub fn push(&mut self, screen: impl Into<Screen>) {
+        let current = self.current();
+        self.stack.0.push(current);
+        self.set(screen.into());
+    }
+
+    // AN EMPTY STACK FALLS BACK TO THE MAIN MENU
+    pub fn pop(&mut self) {
+        let screen = self.stack.0.pop().unwrap_or(Screen::App(AppState::Menu));
+        self.set(screen);
+    }
+
+    // LEAVES THE MENUS ENTIRELY, E.G. INTO A RUN, SO THERE IS NOTHING TO POP BACK TO
+    pub fn goto(&mut self, screen: impl Into<Screen>) {
+        self.stack.0.clear();
+        self.set(screen.into());
+    }
+
+    fn set(&mut self, screen: Screen) {
+        match screen {
+            Screen::App(state) => {
+                self.next_app_state.set(state);
+                info!("[MODIFIED] AppState >> {:?}", state);
             }
-            Interaction::None => {
-                *text_color = BUTTON_TEXT_COLOR_DEFAULT.into();
-                *background_color = BUTTON_BACKGROUND_COLOR_DEFAULT.into();
-                border_color.0 = BUTTON_BORDER_COLOR_DEFAULT.into();
+            Screen::Pause(state) => {
+                self.next_pause_state.set(state);
+                info!("[MODIFIED] PauseState >> {:?}", state);
             }
         }
     }
 }
 
-fn escape_to_menu(
+// FOCUS NAVIGATION
+
+// where a button sits in its screen's focus order, rows for up/down, columns for left/right
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Focusable {
+    pub row: usize,
+    pub column: usize,
+}
+impl Focusable {
+    pub fn at(row: usize, column: usize) -> Self {
+        Self { row, column }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Focus {
+    pub entity: Option<Entity>,
+    pressed: bool, // activated by key or button, released the next frame
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FocusMove {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+const FOCUS_KEYS: [(KeyCode, FocusMove); 8] = [
+    (KeyCode::ArrowUp, FocusMove::Up),
+    (KeyCode::KeyW, FocusMove::Up),
+    (KeyCode::ArrowDown, FocusMove::Down),
+    (KeyCode::KeyS, FocusMove::Down),
+    (KeyCode::ArrowLeft, FocusMove::Left),
+    (KeyCode::KeyA, FocusMove::Left),
+    (KeyCode::ArrowRight, FocusMove::Right),
+    (KeyCode::KeyD, FocusMove::Right),
+];
+
+const FOCUS_BUTTONS: [(GamepadButton, FocusMove); 4] = [
+    (GamepadButton::DPadUp, FocusMove::Up),
+    (GamepadButton::DPadDown, FocusMove::Down),
+    (GamepadButton::DPadLeft, FocusMove::Left),
+    (GamepadButton::DPadRight, FocusMove::Right),
+];
+
+// A KEYBOARD PRESS ONLY LASTS ONE FRAME, UNLIKE A HELD MOUSE BUTTON
+fn release_focus(mut focus: ResMut<Focus>, mut query_interaction: Query<&mut Interaction>) {
+    if !focus.pressed {
+        return;
+    }
+    focus.pressed = false;
+    let Some(entity) = focus.entity else {
+        return;
+    };
+    if let Ok(mut interaction) = query_interaction.get_mut(entity) {
+        interaction.set_if_neq(Interaction::None);
+    }
+}
+
+fn navigate_focus(
     keys: Res<ButtonInput<KeyCode>>,
-    current_state: Res<State<AppState>>,
-    mut next_state: ResMut<NextState<AppState>>,
+    gamepads: Query<&Gamepad>,
+    mut focus: ResMut<Focus>,
+    query_focusable: Query<(Entity, &Focusable, Ref<Interaction>)>,
 ) {
-    if keys.just_pressed(KeyCode::Escape) {
-        match current_state.get() {
-            AppState::Settings => {
-                next_state.set(AppState::Menu);
-                info!("[MODIFIED] Appstate >> Settings");
+    // THE MOUSE MOVES FOCUS TOO SO ONLY ONE BUTTON IS EVER HIGHLIGHTED
+    if let Some((entity, _, _)) = query_focusable.iter().find(|(_, _, interaction)| {
+        interaction.is_changed() && **interaction == Interaction::Hovered
+    }) {
+        focus.entity = Some(entity);
+    }
+
+    let Some(direction) = FOCUS_KEYS
+        .iter()
+        .find(|(key, _)| keys.just_pressed(*key))
+        .map(|(_, direction)| *direction)
+        .or_else(|| {
+            FOCUS_BUTTONS
+                .iter()
+                .find(|(button, _)| gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)))
+                .map(|(_, direction)| *direction)
+        })
+    else {
+        return;
+    };
+
+    let mut order: Vec<(Focusable, Entity)> = query_focusable
+        .iter()
+        .map(|(entity, focusable, _)| (*focusable, entity))
+        .collect();
+    if order.is_empty() {
+        return;
+    }
+    order.sort();
+    // NOTHING FOCUSED YET, OR THE FOCUSED BUTTON WAS DESPAWNED WITH ITS SCREEN
+    let Some(current) = focus
+        .entity
+        .and_then(|entity| order.iter().position(|(_, e)| *e == entity))
+    else {
+        focus.entity = Some(order[0].1);
+        return;
+    };
+
+    let single_row = order.iter().all(|(f, _)| f.row == order[0].0.row);
+    let next = match direction {
+        FocusMove::Left => (current + order.len() - 1) % order.len(),
+        FocusMove::Right => (current + 1) % order.len(),
+        FocusMove::Up if single_row => (current + order.len() - 1) % order.len(),
+        FocusMove::Down if single_row => (current + 1) % order.len(),
+        FocusMove::Up | FocusMove::Down => {
+            let mut rows: Vec<usize> = order.iter().map(|(f, _)| f.row).collect();
+            rows.dedup();
+            let (from, _) = order[current];
+            let index = rows.iter().position(|row| *row == from.row).unwrap_or(0);
+            let row = match direction {
+                FocusMove::Up => rows[(index + rows.len() - 1) % rows.len()],
+                _ => rows[(index + 1) % rows.len()],
+            };
+            // KEEP THE SAME COLUMN, OR THE NEAREST ONE IF THE ROW IS SHORTER
+            order
+                .iter()
+                .enumerate()
+                .filter(|(_, (f, _))| f.row == row)
+                .min_by_key(|(_, (f, _))| f.column.abs_diff(from.column))
+                .map(|(i, _)| i)
+                .unwrap_or(current)
+        }
+    };
+    focus.entity = Some(order[next].1);
+}
+
+// PRESSING THE FOCUSED BUTTON LETS EVERY SCREEN'S OWN INTERACTION HANDLING DO THE REST
+fn activate_focus(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut focus: ResMut<Focus>,
+    mut query_interaction: Query<&mut Interaction, With<Focusable>>,
+) {
+    let activated = keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+    if !activated {
+        return;
+    }
+    let Some(entity) = focus.entity else {
+        return;
+    };
+    if let Ok(mut interaction) = query_interaction.get_mut(entity) {
+        *interaction = Interaction::Pressed;
+        focus.pressed = true;
+    }
+}
+
+// THE FOCUSED BUTTON LOOKS HOVERED, AFTER THE SCREENS HAVE SET THEIR OWN COLOURS
+fn highlight_focus(
+    focus: Res<Focus>,
+    mut previous: Local<Option<Entity>>,
+    mut query_button: Query<(&Interaction, &mut BackgroundColor, &Children), With<Focusable>>,
+    mut text_color_query: Query<&mut TextColor>,
+    theme: Res<Theme>,
+) {
+    let (background, text) = (&theme.colors.button_background, &theme.colors.button_text);
+    if *previous != focus.entity {
+        if let Some(entity) = previous.take() {
+            if let Ok((Interaction::None, mut background_color, children)) =
+                query_button.get_mut(entity)
+            {
+                background_color.0 = background.default;
+                if let Ok(mut text_color) = text_color_query.get_mut(children[0]) {
+                    text_color.0 = text.default;
+                }
             }
-            _ => {}
+        }
+        *previous = focus.entity;
+    }
+    let Some(entity) = focus.entity else {
+        return;
+    };
+    let Ok((Interaction::None, mut background_color, children)) = query_button.get_mut(entity)
+    else {
+        return;
+    };
+    if background_color.0 != background.hovered {
+        background_color.0 = background.hovered;
+    }
+    if let Ok(mut text_color) = text_color_query.get_mut(children[0]) {
+        if text_color.0 != text.hovered {
+            text_color.0 = text.hovered;
         }
     }
 }
@@ -1,8 +1,7 @@
-use crate::{Damage, Title, ID};
+use crate::{loading::RonAssetPlugin, Damage, Title, ID};
 use bevy::prelude::*;
-use ron::de::from_reader;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, fs::File};
+use std::fmt::Display;
 
 pub struct WeaponPlugin;
 impl Plugin for WeaponPlugin {
@@ -11,27 +10,15 @@ impl Plugin for WeaponPlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app.insert_resource(Weapons::init());
+        app.add_plugins(RonAssetPlugin::<Weapons>::new(&["weapons.ron"]));
     }
 }
 
-#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[derive(Asset, TypePath, Resource, Clone, Default, Serialize, Deserialize)]
 pub struct Weapons(pub Vec<Weapon>);
 impl Weapons {
-    fn init() -> Self {
-        let input_path = format!("{}/ron/weapons.ron", env!("CARGO_MANIFEST_DIR"));
-        let f = File::open(input_path.clone()).expect("Failed opening file");
-        let weapons: Weapons = match from_reader(f) {
-            Ok(x) => {
-                info!("[INITIALIZED] Weapons: {}", x);
-                x
-            }
-            Err(e) => {
-                eprintln!("[ERROR] Could not deserialize {}. \n{}", input_path, e);
-                Weapons::default()
-            }
-        };
-        weapons
+    pub fn get(&self, id: WeaponID) -> Option<&Weapon> {
+        self.0.iter().find(|weapon| *weapon.id.0 == *id.0)
     }
 }
 impl Display for Weapons {